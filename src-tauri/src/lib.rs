@@ -29,17 +29,24 @@ struct ObsRuntime {
     scene_order: Vec<String>,
     output_record: *mut obs::obs_output,
     record_video_encoder: *mut obs::obs_encoder,
-    record_audio_encoder: *mut obs::obs_encoder,
+    record_audio_encoders: Vec<*mut obs::obs_encoder>,
     output_stream: *mut obs::obs_output,
     stream_service: *mut obs::obs_service,
     stream_video_encoder: *mut obs::obs_encoder,
     stream_audio_encoder: *mut obs::obs_encoder,
+    stream_encoder_signature: String,
+    stream_targets: HashMap<String, StreamTargetState>,
+    replay_buffer: Option<ReplayBufferState>,
     preview_view: *mut obs::obs_view,
     preview_texrender: *mut obs::gs_texrender_t,
     last_record_path: Option<PathBuf>,
+    record_started_at: Option<std::time::Instant>,
+    record_paused_at: Option<std::time::Instant>,
+    record_paused_total: std::time::Duration,
     video_encoder_preference: VideoEncoderPreference,
     scene_resolution: String,
     planner_init: Option<PlannerInit>,
+    program_transition: Option<TransitionState>,
 }
 
 impl Default for ObsRuntime {
@@ -52,17 +59,24 @@ impl Default for ObsRuntime {
             scene_order: Vec::new(),
             output_record: std::ptr::null_mut(),
             record_video_encoder: std::ptr::null_mut(),
-            record_audio_encoder: std::ptr::null_mut(),
+            record_audio_encoders: Vec::new(),
             output_stream: std::ptr::null_mut(),
             stream_service: std::ptr::null_mut(),
             stream_video_encoder: std::ptr::null_mut(),
             stream_audio_encoder: std::ptr::null_mut(),
+            stream_encoder_signature: String::new(),
+            stream_targets: HashMap::new(),
+            replay_buffer: None,
             preview_view: std::ptr::null_mut(),
             preview_texrender: std::ptr::null_mut(),
             last_record_path: None,
+            record_started_at: None,
+            record_paused_at: None,
+            record_paused_total: std::time::Duration::ZERO,
             video_encoder_preference: VideoEncoderPreference::default(),
             scene_resolution: "1920x1080".to_string(),
             planner_init: None,
+            program_transition: None,
         }
     }
 }
@@ -89,6 +103,30 @@ impl SceneState {
     }
 }
 
+struct StreamTargetState {
+    output: *mut obs::obs_output,
+    service: *mut obs::obs_service,
+    video_encoder: *mut obs::obs_encoder,
+    audio_encoder: *mut obs::obs_encoder,
+    encoder_signature: String,
+    shared_encoders: bool,
+}
+
+struct ReplayBufferState {
+    output: *mut obs::obs_output,
+    video_encoder: *mut obs::obs_encoder,
+    audio_encoder: *mut obs::obs_encoder,
+    output_path: String,
+}
+
+// Transition source feeding output channel 0; scenes are switched through it.
+struct TransitionState {
+    id: String,
+    kind: settings::core::TransitionKind,
+    source: *mut obs::obs_source,
+    duration_ms: u32,
+}
+
 struct ObsState {
     runtime: Mutex<ObsRuntime>,
 }
@@ -161,6 +199,24 @@ fn obs_set_current_scene(state: tauri::State<ObsState>, name: String) -> Result<
     scenes::scene_ops::set_current_scene(state, name)
 }
 
+#[tauri::command]
+fn obs_list_transitions(state: tauri::State<ObsState>) -> Result<Vec<TransitionInfo>, String> {
+    runtime::transitions::list_transitions(state)
+}
+
+#[tauri::command]
+fn obs_configure_transition(
+    state: tauri::State<ObsState>,
+    update: TransitionUpdate,
+) -> Result<TransitionInfo, String> {
+    runtime::transitions::configure_transition(state, update)
+}
+
+#[tauri::command]
+fn obs_select_transition(state: tauri::State<ObsState>, id: String) -> Result<TransitionInfo, String> {
+    runtime::transitions::select_transition(state, id)
+}
+
 #[tauri::command]
 fn obs_create_scene(state: tauri::State<ObsState>, name: String) -> Result<String, String> {
     scenes::scene_ops::create_scene(state, name)
@@ -322,6 +378,11 @@ fn load_rtmp_services_json() -> Result<String, String> {
     settings::core::load_rtmp_services_json()
 }
 
+#[tauri::command]
+fn list_rtmp_services() -> Result<Vec<RtmpService>, String> {
+    settings::services::list_rtmp_services()
+}
+
 #[tauri::command]
 fn settings_get() -> Result<AppSettings, String> {
     settings::core::settings_get()
@@ -478,6 +539,11 @@ fn obs_is_initialized(state: tauri::State<ObsState>) -> Result<bool, String> {
     runtime::state::is_initialized(state)
 }
 
+#[tauri::command]
+fn obs_is_offscreen(state: tauri::State<ObsState>) -> Result<bool, String> {
+    runtime::state::is_offscreen(state)
+}
+
 #[tauri::command]
 fn obs_version() -> Result<String, String> {
     runtime::state::version()
@@ -579,16 +645,144 @@ fn obs_stop_recording(state: tauri::State<ObsState>) -> Result<String, String> {
     runtime::recording::stop_recording(state)
 }
 
+#[tauri::command]
+fn obs_preview_record_path(state: tauri::State<ObsState>, output_path: String) -> Result<String, String> {
+    runtime::helpers::preview_record_path(state, output_path)
+}
+
+#[tauri::command]
+fn obs_split_recording(state: tauri::State<ObsState>) -> Result<Vec<String>, String> {
+    runtime::record_split::split_recording(state)
+}
+
+#[tauri::command]
+fn obs_add_recording_chapter(
+    state: tauri::State<ObsState>,
+    name: Option<String>,
+) -> Result<RecordingChapter, String> {
+    runtime::record_chapters::add_recording_chapter(state, name)
+}
+
+#[tauri::command]
+fn obs_list_recording_history(query: Option<String>) -> Result<Vec<RecordingHistoryEntry>, String> {
+    runtime::recording_history::list_recording_history(query)
+}
+
+#[tauri::command]
+fn obs_reveal_recording(id: String) -> Result<String, String> {
+    runtime::recording_history::reveal_recording(&id)
+}
+
+#[tauri::command]
+fn obs_delete_recording(id: String, delete_files: Option<bool>) -> Result<String, String> {
+    runtime::recording_history::delete_recording(&id, delete_files.unwrap_or(false))
+}
+
+#[tauri::command]
+fn obs_remux_file(path: String, delete_source: Option<bool>) -> Result<RemuxJob, String> {
+    runtime::remux::enqueue_remux(&path, delete_source.unwrap_or(false))
+}
+
+#[tauri::command]
+fn obs_list_remux_jobs() -> Result<Vec<RemuxJob>, String> {
+    runtime::remux::list_remux_jobs()
+}
+
+#[tauri::command]
+fn obs_remux_tools_status() -> RemuxToolsStatus {
+    runtime::remux::remux_tools_status()
+}
+
+#[tauri::command]
+fn obs_clear_remux_jobs() -> Result<usize, String> {
+    runtime::remux::clear_finished_remux_jobs()
+}
+
+#[tauri::command]
+fn obs_pause_recording(state: tauri::State<ObsState>) -> Result<String, String> {
+    runtime::recording::pause_recording(state)
+}
+
+#[tauri::command]
+fn obs_resume_recording(state: tauri::State<ObsState>) -> Result<String, String> {
+    runtime::recording::resume_recording(state)
+}
+
+#[tauri::command]
+fn obs_get_recording_state(state: tauri::State<ObsState>) -> Result<RecordingState, String> {
+    runtime::recording::get_recording_state(state)
+}
+
+#[tauri::command]
+fn obs_start_replay_buffer(
+    state: tauri::State<ObsState>,
+    output_path: String,
+    max_seconds: Option<i64>,
+    max_megabytes: Option<i64>,
+) -> Result<String, String> {
+    runtime::replay_buffer::start_replay_buffer(state, output_path, max_seconds, max_megabytes)
+}
+
+#[tauri::command]
+fn obs_stop_replay_buffer(state: tauri::State<ObsState>) -> Result<String, String> {
+    runtime::replay_buffer::stop_replay_buffer(state)
+}
+
+#[tauri::command]
+fn obs_save_replay_buffer(state: tauri::State<ObsState>) -> Result<String, String> {
+    runtime::replay_buffer::save_replay_buffer(state)
+}
+
 #[tauri::command]
 fn obs_start_streaming(state: tauri::State<ObsState>, stream_url: String) -> Result<String, String> {
     runtime::streaming::start_streaming(state, stream_url)
 }
 
+#[tauri::command]
+fn obs_start_whip_streaming(state: tauri::State<ObsState>) -> Result<String, String> {
+    runtime::streaming::start_whip_streaming(state)
+}
+
 #[tauri::command]
 fn obs_stop_streaming(state: tauri::State<ObsState>) -> Result<String, String> {
     runtime::streaming::stop_streaming(state)
 }
 
+#[tauri::command]
+fn obs_get_output_states() -> Result<Vec<OutputStateInfo>, String> {
+    runtime::output_state::list_output_states()
+}
+
+#[tauri::command]
+fn obs_get_output_stats(state: tauri::State<ObsState>) -> Result<ObsOutputStats, String> {
+    runtime::stats::get_output_stats(state)
+}
+
+#[tauri::command]
+fn obs_list_stream_targets(state: tauri::State<ObsState>) -> Result<Vec<StreamTargetInfo>, String> {
+    runtime::stream_targets::list_stream_targets(state)
+}
+
+#[tauri::command]
+fn obs_save_stream_target(state: tauri::State<ObsState>, target: StreamTargetConfig) -> Result<String, String> {
+    runtime::stream_targets::save_stream_target(state, target)
+}
+
+#[tauri::command]
+fn obs_remove_stream_target(state: tauri::State<ObsState>, name: String) -> Result<String, String> {
+    runtime::stream_targets::remove_stream_target(state, name)
+}
+
+#[tauri::command]
+fn obs_start_stream_target(state: tauri::State<ObsState>, name: String) -> Result<String, String> {
+    runtime::stream_targets::start_stream_target(state, name)
+}
+
+#[tauri::command]
+fn obs_stop_stream_target(state: tauri::State<ObsState>, name: String) -> Result<String, String> {
+    runtime::stream_targets::stop_stream_target(state, name)
+}
+
 #[tauri::command]
 fn obs_set_encoder_preference(
     state: tauri::State<ObsState>,
@@ -617,6 +811,11 @@ pub fn run() {
                 crate::utils::fs::set_startup_cwd(cwd);
             }
 
+            runtime::output_state::set_app_handle(app.handle().clone());
+            std::thread::spawn(|| {
+                runtime::remux::remux_tools_status();
+            });
+
             if let Err(err) = init_debug_log_file() {
                 eprintln!("debug log file init warning: {err}");
             }
@@ -699,9 +898,13 @@ pub fn run() {
             obs_start,
             obs_shutdown,
             obs_is_initialized,
+            obs_is_offscreen,
             obs_version,
             obs_list_scenes,
             obs_set_current_scene,
+            obs_list_transitions,
+            obs_configure_transition,
+            obs_select_transition,
             obs_create_scene,
             obs_rename_scene,
             obs_remove_scene,
@@ -713,6 +916,10 @@ pub fn run() {
             obs_import_scene_collection,
             obs_set_scene_resolution,
             obs_get_current_scene_resolution,
+            obs_set_video_fps,
+            obs_get_video_info,
+            obs_apply_audio_settings,
+            obs_get_audio_info,
             obs_list_sources,
             obs_list_external_source_types,
             obs_get_graphic_planner_init,
@@ -732,8 +939,32 @@ pub fn run() {
             obs_open_source_interaction,
             obs_start_recording,
             obs_stop_recording,
+            obs_preview_record_path,
+            obs_split_recording,
+            obs_add_recording_chapter,
+            obs_list_recording_history,
+            obs_reveal_recording,
+            obs_delete_recording,
+            obs_remux_file,
+            obs_list_remux_jobs,
+            obs_clear_remux_jobs,
+            obs_remux_tools_status,
+            obs_pause_recording,
+            obs_resume_recording,
+            obs_get_recording_state,
+            obs_start_replay_buffer,
+            obs_stop_replay_buffer,
+            obs_save_replay_buffer,
             obs_start_streaming,
+            obs_start_whip_streaming,
             obs_stop_streaming,
+            obs_get_output_states,
+            obs_get_output_stats,
+            obs_list_stream_targets,
+            obs_save_stream_target,
+            obs_remove_stream_target,
+            obs_start_stream_target,
+            obs_stop_stream_target,
             obs_set_encoder_preference,
             obs_take_screenshot,
             obs_reorder_scene,
@@ -758,6 +989,7 @@ pub fn run() {
             obs_export_profile_obs_to_file,
             obs_import_profile_obs,
             load_rtmp_services_json,
+            list_rtmp_services,
             open_graphic_planner,
             open_browser_dock,
             browser_dock_state,
@@ -778,3 +1010,23 @@ pub fn run() {
 fn obs_set_scene_resolution(state: tauri::State<ObsState>, resolution: String) -> Result<String, String> {
     settings::video_settings::set_scene_resolution(state, resolution)
 }
+
+#[tauri::command]
+fn obs_set_video_fps(state: tauri::State<ObsState>, fps: Option<String>) -> Result<String, String> {
+    settings::video_settings::set_video_fps(state, fps)
+}
+
+#[tauri::command]
+fn obs_get_video_info(state: tauri::State<ObsState>) -> Result<VideoInfo, String> {
+    settings::video_settings::get_video_info(state)
+}
+
+#[tauri::command]
+fn obs_apply_audio_settings(state: tauri::State<ObsState>) -> Result<AudioResetStatus, String> {
+    settings::audio_settings::apply_audio_settings(state)
+}
+
+#[tauri::command]
+fn obs_get_audio_info(state: tauri::State<ObsState>) -> Result<AudioInfo, String> {
+    settings::audio_settings::get_audio_info(state)
+}
@@ -15,6 +15,205 @@ impl Default for VideoEncoderPreference {
 	}
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StreamProtocol {
+	Rtmp,
+	Srt,
+	Rist,
+	Whip,
+}
+
+impl StreamProtocol {
+	pub(crate) fn label(&self) -> &'static str {
+		match self {
+			StreamProtocol::Rtmp => "RTMP",
+			StreamProtocol::Srt => "SRT",
+			StreamProtocol::Rist => "RIST",
+			StreamProtocol::Whip => "WHIP",
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OutputPhase {
+	Starting,
+	Live,
+	Reconnecting,
+	Stopped,
+	Failed,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct OutputStateInfo {
+	pub(crate) label: String,
+	pub(crate) phase: OutputPhase,
+	pub(crate) last_error_code: Option<i64>,
+	pub(crate) last_error: Option<String>,
+	pub(crate) reconnect_attempts: u32,
+	pub(crate) reconnect_timeout_sec: Option<i64>,
+	pub(crate) updated_ms: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DiskSpaceLevel {
+	Warning,
+	Critical,
+}
+
+/// Payload of the `obs-disk-space` event.
+#[derive(Serialize, Clone)]
+pub(crate) struct DiskSpaceEvent {
+	pub(crate) label: String,
+	pub(crate) directory: String,
+	pub(crate) free_mb: u64,
+	pub(crate) threshold_mb: u64,
+	pub(crate) level: DiskSpaceLevel,
+	/// Set when the output was stopped because of this event.
+	pub(crate) stopped: bool,
+	pub(crate) timestamp_ms: u64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct VideoInfo {
+	pub(crate) base_width: u32,
+	pub(crate) base_height: u32,
+	pub(crate) output_width: u32,
+	pub(crate) output_height: u32,
+	pub(crate) fps_num: u32,
+	pub(crate) fps_den: u32,
+	pub(crate) fps: f64,
+	pub(crate) color_format: String,
+	pub(crate) color_space: String,
+	pub(crate) color_range: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct AudioInfo {
+	pub(crate) sample_rate: u32,
+	pub(crate) speakers: String,
+	pub(crate) channels: u32,
+}
+
+#[derive(Serialize)]
+pub(crate) struct AudioResetStatus {
+	pub(crate) info: AudioInfo,
+	pub(crate) applied: bool,
+	pub(crate) restart_required: bool,
+	pub(crate) message: String,
+}
+
+#[derive(Serialize)]
+pub(crate) struct OutputStats {
+	pub(crate) label: String,
+	pub(crate) active: bool,
+	pub(crate) total_bytes: u64,
+	pub(crate) total_frames: i32,
+	pub(crate) frames_dropped: i32,
+	pub(crate) dropped_percent: f64,
+	pub(crate) congestion: f32,
+	pub(crate) connect_time_ms: i32,
+	pub(crate) kbps: f64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RenderStats {
+	pub(crate) total_frames: u32,
+	pub(crate) lagged_frames: u32,
+	pub(crate) lagged_percent: f64,
+	pub(crate) average_frame_time_ms: f64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct ObsOutputStats {
+	pub(crate) outputs: Vec<OutputStats>,
+	pub(crate) render: RenderStats,
+}
+
+#[derive(Serialize)]
+pub(crate) struct RecordingState {
+	pub(crate) active: bool,
+	pub(crate) paused: bool,
+	pub(crate) can_pause: bool,
+	pub(crate) path: Option<String>,
+	/// Recorded time excluding pauses.
+	pub(crate) elapsed_ms: u64,
+	pub(crate) paused_ms: u64,
+	/// Files written so far, including the one currently open.
+	pub(crate) segments: Vec<String>,
+	pub(crate) chapters: Vec<RecordingChapter>,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct RecordingChapter {
+	pub(crate) index: usize,
+	pub(crate) name: String,
+	/// Recording time (pauses excluded) at which the chapter starts.
+	pub(crate) offset_ms: u64,
+	/// Written by the muxer itself rather than into a sidecar file.
+	pub(crate) native: bool,
+	pub(crate) sidecar: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct RecordingHistoryEntry {
+	pub(crate) id: String,
+	/// First file of the recording; `segments` lists every split file.
+	pub(crate) path: String,
+	pub(crate) segments: Vec<String>,
+	pub(crate) started_ms: u64,
+	pub(crate) ended_ms: u64,
+	/// Recorded time with pauses excluded.
+	pub(crate) duration_ms: u64,
+	pub(crate) size_bytes: u64,
+	pub(crate) container: String,
+	pub(crate) video_encoder: Option<String>,
+	pub(crate) audio_encoders: Vec<String>,
+	pub(crate) profile: String,
+	/// Scenes shown during the recording, in order of first appearance.
+	pub(crate) scenes: Vec<String>,
+	/// Set on listing when a file was moved or deleted; not persisted.
+	#[serde(default, skip_deserializing)]
+	pub(crate) missing: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RemuxStatus {
+	Queued,
+	Running,
+	Done,
+	Failed,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct RemuxJob {
+	pub(crate) id: String,
+	pub(crate) source: String,
+	pub(crate) target: String,
+	pub(crate) status: RemuxStatus,
+	/// 0.0-1.0; stays at 0 when the source duration is unknown.
+	pub(crate) progress: f64,
+	pub(crate) error: Option<String>,
+	pub(crate) delete_source: bool,
+	pub(crate) source_deleted: bool,
+	/// Why a requested source deletion was skipped.
+	pub(crate) source_kept_reason: Option<String>,
+	pub(crate) created_ms: u64,
+	pub(crate) finished_ms: Option<u64>,
+}
+
+/// External binaries remuxing runs (REVO_FFMPEG_BIN / REVO_FFPROBE_BIN).
+#[derive(Serialize, Clone)]
+pub(crate) struct RemuxToolsStatus {
+	pub(crate) ffmpeg_bin: String,
+	pub(crate) ffmpeg_available: bool,
+	pub(crate) ffprobe_bin: String,
+	pub(crate) ffprobe_available: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct LibObsActionEvent {
 	pub(crate) timestamp_ms: u64,
@@ -121,6 +320,10 @@ pub(crate) struct AppSettings {
 	#[serde(default)]
 	pub(crate) whip_url: Option<String>,
 	#[serde(default)]
+	pub(crate) whip_bearer_token: Option<String>,
+	#[serde(default)]
+	pub(crate) stream_targets: Option<Vec<StreamTargetConfig>>,
+	#[serde(default)]
 	pub(crate) auto_retry_preview: Option<bool>,
 	#[serde(default)]
 	pub(crate) autorescale_inputs: Option<bool>,
@@ -130,6 +333,74 @@ pub(crate) struct AppSettings {
 	pub(crate) active_profile: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub(crate) struct StreamTargetEncoder {
+	#[serde(default)]
+	pub(crate) video_bitrate: Option<i64>,
+	#[serde(default)]
+	pub(crate) audio_bitrate: Option<i64>,
+	#[serde(default)]
+	pub(crate) encoder_preference: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct StreamTargetConfig {
+	pub(crate) name: String,
+	pub(crate) url: String,
+	#[serde(default)]
+	pub(crate) key: String,
+	#[serde(default)]
+	pub(crate) protocol: Option<StreamProtocol>,
+	#[serde(default)]
+	pub(crate) encoder: Option<StreamTargetEncoder>,
+	/// Catalog service name from services.json; when set, `url` may be empty
+	/// and the output goes through `rtmp_common`.
+	#[serde(default)]
+	pub(crate) service: Option<String>,
+	/// Server name or URL within `service`; the first server is used when unset.
+	#[serde(default)]
+	pub(crate) server: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct RtmpServiceServer {
+	pub(crate) name: String,
+	pub(crate) url: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub(crate) struct RtmpServiceRecommendations {
+	pub(crate) keyint: Option<i64>,
+	pub(crate) max_video_bitrate: Option<i64>,
+	pub(crate) max_audio_bitrate: Option<i64>,
+	pub(crate) max_fps: Option<i64>,
+	pub(crate) bframes: Option<i64>,
+	pub(crate) profile: Option<String>,
+	pub(crate) supported_resolutions: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub(crate) struct RtmpService {
+	pub(crate) name: String,
+	pub(crate) protocol: String,
+	pub(crate) servers: Vec<RtmpServiceServer>,
+	pub(crate) recommended: RtmpServiceRecommendations,
+	pub(crate) supported_video_codecs: Vec<String>,
+	pub(crate) supported_audio_codecs: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct StreamTargetInfo {
+	pub(crate) name: String,
+	pub(crate) url: String,
+	pub(crate) protocol: StreamProtocol,
+	pub(crate) service: Option<String>,
+	pub(crate) has_custom_encoder: bool,
+	pub(crate) active: bool,
+	pub(crate) shared_encoders: bool,
+	pub(crate) state: Option<OutputStateInfo>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct PluginInfo {
 	pub(crate) name: String,
@@ -174,6 +445,34 @@ pub(crate) struct PlannerInitInput {
 	pub(crate) scene_name: Option<String>,
 }
 
+#[derive(Serialize)]
+pub(crate) struct TransitionInfo {
+	pub(crate) id: String,
+	pub(crate) name: String,
+	pub(crate) kind: String,
+	pub(crate) duration_ms: u32,
+	pub(crate) params: HashMap<String, String>,
+	pub(crate) selected: bool,
+	/// Whether this build can run the kind (libobs module loaded).
+	pub(crate) supported: bool,
+	/// Currently feeding the program output.
+	pub(crate) live: bool,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TransitionUpdate {
+	/// New entry when absent.
+	#[serde(default)]
+	pub(crate) id: Option<String>,
+	#[serde(default)]
+	pub(crate) name: Option<String>,
+	#[serde(default)]
+	pub(crate) kind: Option<String>,
+	/// Merged into the stored params; empty values remove a key.
+	#[serde(default)]
+	pub(crate) params: HashMap<String, String>,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct SourceUpdate {
 	pub(crate) id: String,
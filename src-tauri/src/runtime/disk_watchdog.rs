@@ -0,0 +1,174 @@
+// Free-space watchdog for outputs that write to disk (recording, replay buffer).
+
+use tauri::Manager;
+
+pub(crate) const DISK_SPACE_EVENT: &str = "obs-disk-space";
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const MB: u64 = 1024 * 1024;
+
+struct DiskWatch {
+	directory: std::path::PathBuf,
+	limits: crate::settings::core::DiskSpaceSettings,
+	// Number of warning thresholds already reported; re-armed when space recovers.
+	warned: usize,
+	generation: u64,
+}
+
+static WATCHES: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, DiskWatch>>> =
+	std::sync::OnceLock::new();
+static WATCH_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn watches() -> &'static std::sync::Mutex<std::collections::HashMap<String, DiskWatch>> {
+	WATCHES.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+fn crossed_thresholds(limits: &crate::settings::core::DiskSpaceSettings, free_mb: u64) -> usize {
+	limits.warn_mb.iter().filter(|t| free_mb < **t).count()
+}
+
+/// Rejects a start when the target filesystem is already below the hard limit.
+pub(crate) fn ensure_space_for_start(directory: &std::path::Path) -> Result<(), String> {
+	let settings = crate::settings::core::settings_get()?;
+	let limits = crate::settings::core::disk_space_settings(&settings)?;
+	if limits.stop_mb == 0 {
+		return Ok(());
+	}
+	let Some(free) = crate::utils::fs::available_space(directory) else {
+		return Ok(());
+	};
+	let free_mb = free / MB;
+	if free_mb < limits.stop_mb {
+		return Err(format!(
+			"Not enough disk space on {}: {free_mb} MB free, at least {} MB required",
+			directory.to_string_lossy(),
+			limits.stop_mb
+		));
+	}
+	Ok(())
+}
+
+/// Starts watching `directory` for the output `label`. Returns a warning when
+/// free space is already under one of the warning thresholds.
+pub(crate) fn begin_watch(label: &str, directory: &std::path::Path) -> Option<String> {
+	let limits = crate::settings::core::settings_get()
+		.ok()
+		.and_then(|s| crate::settings::core::disk_space_settings(&s).ok())
+		.unwrap_or_default();
+	let free_mb = crate::utils::fs::available_space(directory).map(|b| b / MB);
+	let warned = free_mb.map(|f| crossed_thresholds(&limits, f)).unwrap_or(0);
+	let warning = match (free_mb, warned) {
+		(Some(free_mb), n) if n > 0 => Some(format!(
+			"low disk space: {free_mb} MB free on {}",
+			directory.to_string_lossy()
+		)),
+		_ => None,
+	};
+	let enabled = limits.stop_mb > 0 || !limits.warn_mb.is_empty();
+
+	let generation = WATCH_GENERATION.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+	if let Ok(mut guard) = watches().lock() {
+		if enabled {
+			guard.insert(
+				label.to_string(),
+				DiskWatch {
+					directory: directory.to_path_buf(),
+					limits,
+					warned,
+					generation,
+				},
+			);
+		} else {
+			guard.remove(label);
+		}
+	}
+	if enabled {
+		spawn_watch(label.to_string(), generation);
+	}
+	warning
+}
+
+pub(crate) fn end_watch(label: &str) {
+	if let Ok(mut guard) = watches().lock() {
+		guard.remove(label);
+	}
+}
+
+fn emit_disk_event(event: crate::DiskSpaceEvent) {
+	crate::logging::debug::push_debug_log_entry(
+		format!("disk_space:{}", event.label),
+		serde_json::to_value(&event).ok(),
+	);
+	if let Some(handle) = super::output_state::app_handle() {
+		use tauri::Emitter;
+		let _ = handle.emit(DISK_SPACE_EVENT, &event);
+	}
+}
+
+// Stops through the regular command paths so files are finalized and the
+// recording's post-processing (segments, remux) still runs.
+fn stop_output(label: &str) -> bool {
+	let Some(app) = super::output_state::app_handle() else {
+		return false;
+	};
+	let result = match label {
+		super::output_state::RECORD_LABEL => super::recording::stop_recording(app.state()),
+		super::output_state::REPLAY_LABEL => super::replay_buffer::stop_replay_buffer(app.state()),
+		_ => return false,
+	};
+	result.is_ok()
+}
+
+fn spawn_watch(label: String, generation: u64) {
+	std::thread::spawn(move || loop {
+		std::thread::sleep(CHECK_INTERVAL);
+		let (directory, limits, warned) = {
+			let Ok(guard) = watches().lock() else {
+				return;
+			};
+			match guard.get(&label) {
+				Some(watch) if watch.generation == generation => {
+					(watch.directory.clone(), watch.limits.clone(), watch.warned)
+				}
+				_ => return,
+			}
+		};
+		let Some(free) = crate::utils::fs::available_space(&directory) else {
+			continue;
+		};
+		let free_mb = free / MB;
+		let event = |threshold_mb: u64, level: crate::DiskSpaceLevel, stopped: bool| {
+			crate::DiskSpaceEvent {
+				label: label.clone(),
+				directory: directory.to_string_lossy().to_string(),
+				free_mb,
+				threshold_mb,
+				level,
+				stopped,
+				timestamp_ms: super::output_state::now_ms(),
+			}
+		};
+
+		if limits.stop_mb > 0 && free_mb < limits.stop_mb {
+			end_watch(&label);
+			let stopped = stop_output(&label);
+			emit_disk_event(event(limits.stop_mb, crate::DiskSpaceLevel::Critical, stopped));
+			return;
+		}
+
+		let crossed = crossed_thresholds(&limits, free_mb);
+		if crossed != warned {
+			if let Ok(mut guard) = watches().lock() {
+				if let Some(watch) = guard.get_mut(&label).filter(|w| w.generation == generation) {
+					watch.warned = crossed;
+				}
+			}
+		}
+		if crossed > warned {
+			emit_disk_event(event(
+				limits.warn_mb[crossed - 1],
+				crate::DiskSpaceLevel::Warning,
+				false,
+			));
+		}
+	});
+}
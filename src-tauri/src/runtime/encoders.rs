@@ -20,33 +20,44 @@ pub(crate) fn set_encoder_preference(
 
 pub(crate) fn create_video_encoder(
 	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
 ) -> *mut revo_lib::obs::obs_encoder {
+	if let Some(value) = encoder_settings.video_encoder.as_deref() {
+		return match resolve_encoder_id(value) {
+			Ok(id) if is_x264_encoder_id(&id) => create_x264_encoder(encoder_settings),
+			Ok(id) => try_create_encoder(&id, "revo_video", encoder_settings),
+			Err(_) => std::ptr::null_mut(),
+		};
+	}
 	match preference {
-		crate::VideoEncoderPreference::Software => create_x264_encoder(),
+		crate::VideoEncoderPreference::Software => create_x264_encoder(encoder_settings),
 		crate::VideoEncoderPreference::Hardware => {
-			let hw = create_hardware_encoder();
+			let hw = create_hardware_encoder(encoder_settings);
 			if !hw.is_null() {
 				hw
 			} else {
-				create_x264_encoder()
+				create_x264_encoder(encoder_settings)
 			}
 		}
 	}
 }
 
-pub(crate) fn create_hardware_encoder() -> *mut revo_lib::obs::obs_encoder {
-	let candidates = [
-		"h264_nvenc",
-		"ffmpeg_nvenc",
-		"h264_qsv",
-		"h264_vaapi",
-		"h264_amf",
-	];
-	for id in candidates.iter() {
+const HARDWARE_ENCODER_CANDIDATES: [&str; 5] = [
+	"h264_nvenc",
+	"ffmpeg_nvenc",
+	"h264_qsv",
+	"h264_vaapi",
+	"h264_amf",
+];
+
+pub(crate) fn create_hardware_encoder(
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_encoder {
+	for id in HARDWARE_ENCODER_CANDIDATES.iter() {
 		if !encoder_id_available(id) {
 			continue;
 		}
-		let enc = try_create_encoder(id, "revo_video_hw");
+		let enc = try_create_encoder(id, "revo_video_hw", encoder_settings);
 		if !enc.is_null() {
 			return enc;
 		}
@@ -77,22 +88,192 @@ pub(crate) fn encoder_id_available(id: &str) -> bool {
 	false
 }
 
+// Profile values are either libobs encoder ids or the short names the settings
+// UI offers by default.
+fn encoder_id_candidates(value: &str) -> Vec<&str> {
+	match value.to_lowercase().as_str() {
+		"x264" | "obs_x264" => vec!["obs_x264", "x264"],
+		"h264_nvenc" | "nvenc" => vec!["h264_nvenc", "jim_nvenc", "ffmpeg_nvenc"],
+		"h264_vaapi" | "vaapi" => vec!["h264_vaapi", "ffmpeg_vaapi"],
+		"aac" => vec!["ffmpeg_aac"],
+		"opus" => vec!["ffmpeg_opus"],
+		_ => vec![value],
+	}
+}
+
+pub(crate) fn resolve_encoder_id(value: &str) -> Result<String, String> {
+	let value = value.trim();
+	encoder_id_candidates(value)
+		.into_iter()
+		.find(|id| encoder_id_available(id))
+		.map(str::to_string)
+		.ok_or_else(|| format!("encoder '{value}' is not available"))
+}
+
+/// Codec an encoder produces, with PCM sample formats folded into `pcm`.
+pub(crate) fn encoder_codec(id: &str) -> Option<String> {
+	let id_c = std::ffi::CString::new(id).ok()?;
+	let codec = unsafe {
+		super::helpers::cstr_to_string(revo_lib::obs::obs_get_encoder_codec(id_c.as_ptr()))
+	};
+	let codec = codec.trim().to_lowercase();
+	if codec.is_empty() {
+		None
+	} else if codec.starts_with("pcm") {
+		Some("pcm".to_string())
+	} else {
+		Some(codec)
+	}
+}
+
+/// Checks the running video format against the codec an output will encode.
+pub(crate) fn ensure_video_format_supported(codec: &str) -> Result<(), String> {
+	let format = unsafe {
+		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
+		if !revo_lib::obs::obs_get_video_info(&mut ovi as *mut _) {
+			return Ok(());
+		}
+		super::helpers::color_format_from_obs(ovi.output_format)
+	};
+	match format {
+		Some(format) => crate::settings::core::validate_video_color_for_codec(format, codec),
+		None => Ok(()),
+	}
+}
+
+pub(crate) fn is_x264_encoder_id(id: &str) -> bool {
+	id.eq_ignore_ascii_case("obs_x264") || id.eq_ignore_ascii_case("x264")
+}
+
+// The encoder create_video_encoder will pick, without creating it.
+fn streaming_video_encoder_id(
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Result<String, String> {
+	if let Some(value) = encoder_settings.video_encoder.as_deref() {
+		return resolve_encoder_id(value);
+	}
+	let hardware: &[&str] = match preference {
+		crate::VideoEncoderPreference::Hardware => &HARDWARE_ENCODER_CANDIDATES,
+		crate::VideoEncoderPreference::Software => &[],
+	};
+	hardware
+		.iter()
+		.chain(["obs_x264", "x264"].iter())
+		.find(|id| encoder_id_available(id))
+		.map(|id| id.to_string())
+		.ok_or_else(|| "no H.264 video encoder is available".to_string())
+}
+
+// String values a list property offers; None when the encoder has no such
+// property or the list is not string-valued.
+unsafe fn string_list_values(
+	props: *mut revo_lib::obs::obs_properties_t,
+	key: &str,
+) -> Option<Vec<String>> {
+	let key_c = std::ffi::CString::new(key).ok()?;
+	let prop = revo_lib::obs::obs_properties_get(props, key_c.as_ptr());
+	if prop.is_null()
+		|| revo_lib::obs::obs_property_get_type(prop)
+			!= revo_lib::obs::obs_property_type_OBS_PROPERTY_LIST
+		|| revo_lib::obs::obs_property_list_format(prop)
+			!= revo_lib::obs::obs_combo_format_OBS_COMBO_FORMAT_STRING
+	{
+		return None;
+	}
+	let count = revo_lib::obs::obs_property_list_item_count(prop);
+	Some(
+		(0..count)
+			.map(|i| super::helpers::cstr_to_string(revo_lib::obs::obs_property_list_item_string(prop, i)))
+			.filter(|v| !v.is_empty())
+			.collect(),
+	)
+}
+
+/// Checks the streaming settings against the properties of the encoder that
+/// will actually be created (`streamingVideoEncoder`, else the preference).
+pub(crate) fn validate_streaming_video_encoder(
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Result<(), String> {
+	let id = streaming_video_encoder_id(preference, encoder_settings)
+		.map_err(|e| format!("Invalid streaming encoder settings: {e}"))?;
+	let mut errors: Vec<String> = Vec::new();
+	match encoder_codec(&id).as_deref() {
+		Some("h264") | None => {}
+		Some(codec) => errors.push(format!("{id} encodes {codec}; stream outputs need H.264")),
+	}
+	unsafe {
+		let id_c = std::ffi::CString::new(id.as_str()).unwrap_or_default();
+		let props = revo_lib::obs::obs_get_encoder_properties(id_c.as_ptr());
+		if !props.is_null() {
+			for (key, label, value) in [
+				("rate_control", "streamingRateControl", Some(encoder_settings.rate_control.as_str())),
+				("profile", "streamingProfile", encoder_settings.profile.as_deref()),
+			] {
+				let (Some(value), Some(allowed)) = (value, string_list_values(props, key)) else {
+					continue;
+				};
+				if !allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+					errors.push(format!("{label} '{value}' is not one of {} ({id})", allowed.join(", ")));
+				}
+			}
+			revo_lib::obs::obs_properties_destroy(props);
+		}
+	}
+	if !errors.is_empty() {
+		return Err(format!("Invalid streaming encoder settings: {}", errors.join("; ")));
+	}
+	Ok(())
+}
+
+// Writes the common rate-control keys; x264-only keys are left to the caller.
+unsafe fn apply_common_encoder_settings(
+	settings: *mut revo_lib::obs::obs_data,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) {
+	let bitrate_key = std::ffi::CString::new("bitrate").unwrap();
+	revo_lib::obs::obs_data_set_int(settings, bitrate_key.as_ptr(), encoder_settings.bitrate);
+
+	let rc_key = std::ffi::CString::new("rate_control").unwrap();
+	let rc_val = std::ffi::CString::new(encoder_settings.rate_control.as_str()).unwrap();
+	revo_lib::obs::obs_data_set_string(settings, rc_key.as_ptr(), rc_val.as_ptr());
+
+	let keyint_key = std::ffi::CString::new("keyint_sec").unwrap();
+	revo_lib::obs::obs_data_set_int(settings, keyint_key.as_ptr(), encoder_settings.keyint_sec);
+
+	if let Some(profile) = encoder_settings.profile.as_deref() {
+		let profile_key = std::ffi::CString::new("profile").unwrap();
+		let profile_val = std::ffi::CString::new(profile).unwrap();
+		revo_lib::obs::obs_data_set_string(settings, profile_key.as_ptr(), profile_val.as_ptr());
+	}
+}
+
 pub(crate) fn try_create_encoder(
 	id: &str,
 	name: &str,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
 ) -> *mut revo_lib::obs::obs_encoder {
 	unsafe {
 		let settings = revo_lib::obs::obs_data_create();
 		if !settings.is_null() {
-			let bitrate_key = std::ffi::CString::new("bitrate").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, bitrate_key.as_ptr(), 2500);
-
-			let rc_key = std::ffi::CString::new("rate_control").unwrap();
-			let rc_val = std::ffi::CString::new("CBR").unwrap();
-			revo_lib::obs::obs_data_set_string(settings, rc_key.as_ptr(), rc_val.as_ptr());
+			apply_common_encoder_settings(settings, encoder_settings);
 
-			let keyint_key = std::ffi::CString::new("keyint_sec").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, keyint_key.as_ptr(), 2);
+			// Hardware encoders have no x264opts string, so extra options are
+			// written as individual keys and checked against the encoder's
+			// properties afterwards (see video_encoder_warnings).
+			for (key, value) in &encoder_settings.extra_options {
+				let Ok(key_c) = std::ffi::CString::new(key.as_str()) else {
+					continue;
+				};
+				if let Ok(int_val) = value.parse::<i64>() {
+					revo_lib::obs::obs_data_set_int(settings, key_c.as_ptr(), int_val);
+				} else if let Ok(bool_val) = value.parse::<bool>() {
+					revo_lib::obs::obs_data_set_bool(settings, key_c.as_ptr(), bool_val);
+				} else if let Ok(val_c) = std::ffi::CString::new(value.as_str()) {
+					revo_lib::obs::obs_data_set_string(settings, key_c.as_ptr(), val_c.as_ptr());
+				}
+			}
 		}
 		let id_c = std::ffi::CString::new(id).unwrap();
 		let name_c = std::ffi::CString::new(name).unwrap();
@@ -109,40 +290,59 @@ pub(crate) fn try_create_encoder(
 	}
 }
 
-pub(crate) fn create_x264_encoder() -> *mut revo_lib::obs::obs_encoder {
+pub(crate) fn create_x264_encoder(
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_encoder {
 	unsafe {
 		let settings = revo_lib::obs::obs_data_create();
 		if !settings.is_null() {
-			let key = std::ffi::CString::new("bitrate").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, key.as_ptr(), 2500);
+			apply_common_encoder_settings(settings, encoder_settings);
 
-			let rc_key = std::ffi::CString::new("rate_control").unwrap();
-			let rc_val = std::ffi::CString::new("CBR").unwrap();
-			revo_lib::obs::obs_data_set_string(settings, rc_key.as_ptr(), rc_val.as_ptr());
-
-			let buf_key = std::ffi::CString::new("buffer_size").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, buf_key.as_ptr(), 2500);
-
-			let keyint_key = std::ffi::CString::new("keyint_sec").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, keyint_key.as_ptr(), 2);
+			// The low-latency fallback pins x264 to baseline; hardware encoders
+			// keep their own default profile when none is set.
+			if encoder_settings.low_latency && encoder_settings.profile.is_none() {
+				let profile_key = std::ffi::CString::new("profile").unwrap();
+				let profile_val = std::ffi::CString::new("baseline").unwrap();
+				revo_lib::obs::obs_data_set_string(settings, profile_key.as_ptr(), profile_val.as_ptr());
+			}
 
-			let bframes_key = std::ffi::CString::new("bframes").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, bframes_key.as_ptr(), 0);
+			if encoder_settings.rate_control == "CBR" {
+				let buf_key = std::ffi::CString::new("buffer_size").unwrap();
+				revo_lib::obs::obs_data_set_int(settings, buf_key.as_ptr(), encoder_settings.bitrate);
+			}
 
-			let profile_key = std::ffi::CString::new("profile").unwrap();
-			let profile_val = std::ffi::CString::new("baseline").unwrap();
-			revo_lib::obs::obs_data_set_string(settings, profile_key.as_ptr(), profile_val.as_ptr());
+			if let Some(preset) = encoder_settings.cpu_usage_preset.as_deref() {
+				let preset_key = std::ffi::CString::new("preset").unwrap();
+				let preset_val = std::ffi::CString::new(preset).unwrap();
+				revo_lib::obs::obs_data_set_string(settings, preset_key.as_ptr(), preset_val.as_ptr());
+			}
 
-			let tune_key = std::ffi::CString::new("tune").unwrap();
-			let tune_val = std::ffi::CString::new("zerolatency").unwrap();
-			revo_lib::obs::obs_data_set_string(settings, tune_key.as_ptr(), tune_val.as_ptr());
+			if let Some(tune) = encoder_settings.tune.as_deref() {
+				let tune_key = std::ffi::CString::new("tune").unwrap();
+				let tune_val = std::ffi::CString::new(tune).unwrap();
+				revo_lib::obs::obs_data_set_string(settings, tune_key.as_ptr(), tune_val.as_ptr());
+			}
 
 			let repeat_key = std::ffi::CString::new("repeat_headers").unwrap();
 			revo_lib::obs::obs_data_set_bool(settings, repeat_key.as_ptr(), true);
 
-			let opts_key = std::ffi::CString::new("x264opts").unwrap();
-			let opts_val = std::ffi::CString::new("nal-hrd=cbr:force-cfr=1:open-gop=0:aud=1").unwrap();
-			revo_lib::obs::obs_data_set_string(settings, opts_key.as_ptr(), opts_val.as_ptr());
+			let mut x264opts: Vec<String> = Vec::new();
+			if encoder_settings.low_latency {
+				let bframes_key = std::ffi::CString::new("bframes").unwrap();
+				revo_lib::obs::obs_data_set_int(settings, bframes_key.as_ptr(), 0);
+				x264opts.push("nal-hrd=cbr:force-cfr=1:open-gop=0:aud=1".to_string());
+			}
+			x264opts.extend(
+				encoder_settings
+					.extra_options
+					.iter()
+					.map(|(key, value)| format!("{key}={value}")),
+			);
+			if !x264opts.is_empty() {
+				let opts_key = std::ffi::CString::new("x264opts").unwrap();
+				let opts_val = std::ffi::CString::new(x264opts.join(" ")).unwrap_or_default();
+				revo_lib::obs::obs_data_set_string(settings, opts_key.as_ptr(), opts_val.as_ptr());
+			}
 		}
 		let mut enc = std::ptr::null_mut();
 		for encoder_id in ["obs_x264", "x264"] {
@@ -168,6 +368,52 @@ pub(crate) fn create_x264_encoder() -> *mut revo_lib::obs::obs_encoder {
 	}
 }
 
+// Reports profile options the chosen encoder cannot honor. x264 receives extra
+// options through x264opts, which libobs does not validate up front.
+pub(crate) fn video_encoder_warnings(
+	encoder: *mut revo_lib::obs::obs_encoder,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Vec<String> {
+	let mut warnings = Vec::new();
+	if encoder.is_null() || encoder_settings.low_latency {
+		return warnings;
+	}
+	let id = unsafe {
+		super::helpers::cstr_to_string(revo_lib::obs::obs_encoder_get_id(encoder))
+	};
+	if is_x264_encoder_id(&id) {
+		return warnings;
+	}
+
+	if encoder_settings.cpu_usage_preset.is_some() {
+		warnings.push(format!("CPU usage preset is x264-only and was ignored by {id}"));
+	}
+	if encoder_settings.tune.is_some() {
+		warnings.push(format!("tune is x264-only and was ignored by {id}"));
+	}
+	if encoder_settings.extra_options.is_empty() {
+		return warnings;
+	}
+
+	unsafe {
+		let id_c = std::ffi::CString::new(id.as_str()).unwrap_or_default();
+		let props = revo_lib::obs::obs_get_encoder_properties(id_c.as_ptr());
+		for (key, _) in &encoder_settings.extra_options {
+			let known = !props.is_null()
+				&& std::ffi::CString::new(key.as_str())
+					.map(|key_c| !revo_lib::obs::obs_properties_get(props, key_c.as_ptr()).is_null())
+					.unwrap_or(false);
+			if !known {
+				warnings.push(format!("encoder option '{key}' is not supported by {id}"));
+			}
+		}
+		if !props.is_null() {
+			revo_lib::obs::obs_properties_destroy(props);
+		}
+	}
+	warnings
+}
+
 pub(crate) fn create_audio_encoder() -> *mut revo_lib::obs::obs_encoder {
 	unsafe {
 		let settings = revo_lib::obs::obs_data_create();
@@ -191,21 +437,78 @@ pub(crate) fn create_audio_encoder() -> *mut revo_lib::obs::obs_encoder {
 	}
 }
 
+// ffmpeg-mux writes each audio encoder's name as the stream title, so the
+// track name doubles as the container metadata editors show.
+pub(crate) fn create_track_audio_encoder(
+	track: &crate::settings::core::AudioTrackSettings,
+	encoder_id: &str,
+) -> *mut revo_lib::obs::obs_encoder {
+	let Ok(name) = std::ffi::CString::new(track.name.as_str()) else {
+		return std::ptr::null_mut();
+	};
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if !settings.is_null() {
+			let key = std::ffi::CString::new("bitrate").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, key.as_ptr(), track.bitrate);
+		}
+		let id = std::ffi::CString::new(encoder_id).unwrap_or_default();
+		let enc = revo_lib::obs::obs_audio_encoder_create(
+			id.as_ptr(),
+			name.as_ptr(),
+			settings,
+			track.index.saturating_sub(1),
+			std::ptr::null_mut(),
+		);
+		if !settings.is_null() {
+			revo_lib::obs::obs_data_release(settings);
+		}
+		enc
+	}
+}
+
+/// Container and codec details written into a recording output's settings.
+pub(crate) struct RecordOutputOptions {
+	pub(crate) format_name: String,
+	pub(crate) muxer_settings: String,
+	pub(crate) video_codec: String,
+	pub(crate) audio_codec: String,
+	pub(crate) video_bitrate: i64,
+	pub(crate) audio_bitrate: i64,
+}
+
+// ffmpeg_output encodes raw frames itself and wants FFmpeg encoder names.
+fn ffmpeg_encoder_name(codec: &str) -> &str {
+	match codec {
+		"h264" => "libx264",
+		"hevc" => "libx265",
+		"av1" => "libaom-av1",
+		"vp8" => "libvpx",
+		"vp9" => "libvpx-vp9",
+		"prores" => "prores_ks",
+		"opus" => "libopus",
+		"vorbis" => "libvorbis",
+		"pcm" => "pcm_s16le",
+		other => other,
+	}
+}
+
 pub(crate) fn create_muxer_output_with_path(
 	output_path: &str,
 	prefer_ffmpeg_muxer: bool,
+	options: &RecordOutputOptions,
 ) -> *mut revo_lib::obs::obs_output {
 	unsafe {
 		let name = std::ffi::CString::new("revo_record").unwrap();
 
 		if prefer_ffmpeg_muxer {
-			let out_muxer = create_output_with_id("ffmpeg_muxer", output_path, true, &name);
+			let out_muxer = create_output_with_id("ffmpeg_muxer", output_path, true, &name, options);
 			if !out_muxer.is_null() {
 				return out_muxer;
 			}
 		}
 
-		let out_ffmpeg = create_output_with_id("ffmpeg_output", output_path, false, &name);
+		let out_ffmpeg = create_output_with_id("ffmpeg_output", output_path, false, &name, options);
 		if !out_ffmpeg.is_null() {
 			return out_ffmpeg;
 		}
@@ -219,6 +522,7 @@ pub(crate) unsafe fn create_output_with_id(
 	output_path: &str,
 	prefer_ffmpeg_muxer: bool,
 	name: &std::ffi::CString,
+	options: &RecordOutputOptions,
 ) -> *mut revo_lib::obs::obs_output {
 	let id_c = std::ffi::CString::new(id).unwrap();
 	let settings = revo_lib::obs::obs_output_defaults(id_c.as_ptr());
@@ -237,7 +541,7 @@ pub(crate) unsafe fn create_output_with_id(
 		revo_lib::obs::obs_data_set_string(settings, url_key.as_ptr(), path_val.as_ptr());
 
 		let format_key = std::ffi::CString::new("format").unwrap();
-		let format_val = std::ffi::CString::new("mp4").unwrap();
+		let format_val = std::ffi::CString::new(options.format_name.as_str()).unwrap_or_default();
 		revo_lib::obs::obs_data_set_string(settings, format_key.as_ptr(), format_val.as_ptr());
 
 		let format_name_key = std::ffi::CString::new("format_name").unwrap();
@@ -247,20 +551,28 @@ pub(crate) unsafe fn create_output_with_id(
 			format_val.as_ptr(),
 		);
 
+		if !options.muxer_settings.is_empty() {
+			let muxer_key = std::ffi::CString::new("muxer_settings").unwrap();
+			let muxer_val = std::ffi::CString::new(options.muxer_settings.as_str()).unwrap_or_default();
+			revo_lib::obs::obs_data_set_string(settings, muxer_key.as_ptr(), muxer_val.as_ptr());
+		}
+
 		if !prefer_ffmpeg_muxer {
 			let venc_key = std::ffi::CString::new("video_encoder").unwrap();
-			let venc_val = std::ffi::CString::new("libx264").unwrap();
+			let venc_val =
+				std::ffi::CString::new(ffmpeg_encoder_name(&options.video_codec)).unwrap_or_default();
 			revo_lib::obs::obs_data_set_string(settings, venc_key.as_ptr(), venc_val.as_ptr());
 
 			let aenc_key = std::ffi::CString::new("audio_encoder").unwrap();
-			let aenc_val = std::ffi::CString::new("aac").unwrap();
+			let aenc_val =
+				std::ffi::CString::new(ffmpeg_encoder_name(&options.audio_codec)).unwrap_or_default();
 			revo_lib::obs::obs_data_set_string(settings, aenc_key.as_ptr(), aenc_val.as_ptr());
 
 			let vbit_key = std::ffi::CString::new("video_bitrate").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, vbit_key.as_ptr(), 2500);
+			revo_lib::obs::obs_data_set_int(settings, vbit_key.as_ptr(), options.video_bitrate);
 
 			let abit_key = std::ffi::CString::new("audio_bitrate").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, abit_key.as_ptr(), 160);
+			revo_lib::obs::obs_data_set_int(settings, abit_key.as_ptr(), options.audio_bitrate);
 
 			let gop_key = std::ffi::CString::new("gop_size").unwrap();
 			revo_lib::obs::obs_data_set_int(settings, gop_key.as_ptr(), 250);
@@ -279,18 +591,115 @@ pub(crate) unsafe fn create_output_with_id(
 	out
 }
 
-pub(crate) fn create_ffmpeg_stream_output(rtmp_url: &str) -> *mut revo_lib::obs::obs_output {
+pub(crate) fn create_custom_ffmpeg_output(
+	url: &str,
+	custom: &crate::settings::core::RecordingCustomOutputSettings,
+) -> *mut revo_lib::obs::obs_output {
+	let strings = [
+		("url", url),
+		("format_name", custom.format_name.as_str()),
+		("muxer_settings", custom.muxer_settings.as_str()),
+		("video_encoder", custom.video_encoder.as_str()),
+		("video_settings", custom.video_settings.as_str()),
+		("audio_encoder", custom.audio_encoder.as_str()),
+		("audio_settings", custom.audio_settings.as_str()),
+	];
+	let Ok(strings) = strings
+		.iter()
+		.map(|(k, v)| Ok((std::ffi::CString::new(*k)?, std::ffi::CString::new(*v)?)))
+		.collect::<Result<Vec<_>, std::ffi::NulError>>()
+	else {
+		return std::ptr::null_mut();
+	};
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if !settings.is_null() {
+			for (key, value) in &strings {
+				revo_lib::obs::obs_data_set_string(settings, key.as_ptr(), value.as_ptr());
+			}
+
+			let vbit_key = std::ffi::CString::new("video_bitrate").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, vbit_key.as_ptr(), custom.video_bitrate);
+
+			let abit_key = std::ffi::CString::new("audio_bitrate").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, abit_key.as_ptr(), custom.audio_bitrate);
+
+			let gop_key = std::ffi::CString::new("gop_size").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, gop_key.as_ptr(), 250);
+		}
+
+		let output_name = std::ffi::CString::new("revo_record").unwrap();
+		let output_id = std::ffi::CString::new("ffmpeg_output").unwrap();
+		let output = revo_lib::obs::obs_output_create(
+			output_id.as_ptr(),
+			output_name.as_ptr(),
+			settings,
+			std::ptr::null_mut(),
+		);
+		if !settings.is_null() {
+			revo_lib::obs::obs_data_release(settings);
+		}
+		output
+	}
+}
+
+// GOP length for ffmpeg_output, which takes frames instead of keyint_sec.
+// 0 (auto) keeps x264's own default of 250 frames.
+fn ffmpeg_gop_size(keyint_sec: i64) -> i64 {
+	if keyint_sec <= 0 {
+		return 250;
+	}
+	let fps = unsafe {
+		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
+		if revo_lib::obs::obs_get_video_info(&mut ovi as *mut _) && ovi.fps_den > 0 {
+			ovi.fps_num as f64 / ovi.fps_den as f64
+		} else {
+			30.0
+		}
+	};
+	((keyint_sec as f64 * fps).round() as i64).max(1)
+}
+
+// x264 options for the libx264 fallback, in ffmpeg_output's `key=value` form.
+// Presets picked for another encoder mean nothing to libx264 and are dropped.
+fn ffmpeg_x264_options(encoder_settings: &crate::settings::core::StreamingEncoderSettings) -> String {
+	if !encoder_settings.video_encoder.as_deref().map_or(true, is_x264_encoder_id) {
+		return String::new();
+	}
+	let mut options: Vec<String> = Vec::new();
+	for (key, value) in [
+		("preset", encoder_settings.cpu_usage_preset.as_deref()),
+		("tune", encoder_settings.tune.as_deref()),
+		("profile", encoder_settings.profile.as_deref()),
+	] {
+		if let Some(value) = value {
+			options.push(format!("{key}={value}"));
+		}
+	}
+	options.join(" ")
+}
+
+pub(crate) fn create_ffmpeg_stream_output(
+	url: &str,
+	protocol: crate::StreamProtocol,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_output {
+	let (format, protocol_name) = match protocol {
+		crate::StreamProtocol::Srt => ("mpegts", "srt"),
+		crate::StreamProtocol::Rist => ("mpegts", "rist"),
+		_ => ("flv", "rtmp"),
+	};
 	unsafe {
 		let settings = revo_lib::obs::obs_data_create();
 		if !settings.is_null() {
 			let path_key = std::ffi::CString::new("path").unwrap();
 			let url_key = std::ffi::CString::new("url").unwrap();
-			let path_val = std::ffi::CString::new(rtmp_url).unwrap();
+			let path_val = std::ffi::CString::new(url).unwrap();
 			revo_lib::obs::obs_data_set_string(settings, path_key.as_ptr(), path_val.as_ptr());
 			revo_lib::obs::obs_data_set_string(settings, url_key.as_ptr(), path_val.as_ptr());
 
 			let format_key = std::ffi::CString::new("format").unwrap();
-			let format_val = std::ffi::CString::new("flv").unwrap();
+			let format_val = std::ffi::CString::new(format).unwrap();
 			revo_lib::obs::obs_data_set_string(settings, format_key.as_ptr(), format_val.as_ptr());
 
 			let format_name_key = std::ffi::CString::new("format_name").unwrap();
@@ -301,7 +710,7 @@ pub(crate) fn create_ffmpeg_stream_output(rtmp_url: &str) -> *mut revo_lib::obs:
 			);
 
 			let protocol_key = std::ffi::CString::new("protocol").unwrap();
-			let protocol_val = std::ffi::CString::new("rtmp").unwrap();
+			let protocol_val = std::ffi::CString::new(protocol_name).unwrap();
 			revo_lib::obs::obs_data_set_string(settings, protocol_key.as_ptr(), protocol_val.as_ptr());
 
 			let venc_key = std::ffi::CString::new("video_encoder").unwrap();
@@ -313,13 +722,24 @@ pub(crate) fn create_ffmpeg_stream_output(rtmp_url: &str) -> *mut revo_lib::obs:
 			revo_lib::obs::obs_data_set_string(settings, aenc_key.as_ptr(), aenc_val.as_ptr());
 
 			let vbit_key = std::ffi::CString::new("video_bitrate").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, vbit_key.as_ptr(), 2500);
+			revo_lib::obs::obs_data_set_int(settings, vbit_key.as_ptr(), encoder_settings.bitrate);
 
 			let abit_key = std::ffi::CString::new("audio_bitrate").unwrap();
 			revo_lib::obs::obs_data_set_int(settings, abit_key.as_ptr(), 160);
 
 			let gop_key = std::ffi::CString::new("gop_size").unwrap();
-			revo_lib::obs::obs_data_set_int(settings, gop_key.as_ptr(), 60);
+			revo_lib::obs::obs_data_set_int(
+				settings,
+				gop_key.as_ptr(),
+				ffmpeg_gop_size(encoder_settings.keyint_sec),
+			);
+
+			let x264_options = ffmpeg_x264_options(encoder_settings);
+			if !x264_options.is_empty() {
+				let vset_key = std::ffi::CString::new("video_settings").unwrap();
+				let vset_val = std::ffi::CString::new(x264_options).unwrap_or_default();
+				revo_lib::obs::obs_data_set_string(settings, vset_key.as_ptr(), vset_val.as_ptr());
+			}
 		}
 
 		let output_name = std::ffi::CString::new("revo_stream").unwrap();
@@ -337,12 +757,158 @@ pub(crate) fn create_ffmpeg_stream_output(rtmp_url: &str) -> *mut revo_lib::obs:
 	}
 }
 
+pub(crate) fn stream_protocol_from_url(url: &str) -> Option<crate::StreamProtocol> {
+	let lower = url.trim().to_ascii_lowercase();
+	let scheme = lower.split("://").next().filter(|_| lower.contains("://"))?;
+	match scheme {
+		"rtmp" | "rtmps" => Some(crate::StreamProtocol::Rtmp),
+		"srt" => Some(crate::StreamProtocol::Srt),
+		"rist" => Some(crate::StreamProtocol::Rist),
+		"http" | "https" => Some(crate::StreamProtocol::Whip),
+		_ => None,
+	}
+}
+
+// Builds the output for `protocol`. SRT/RIST URLs are handed to the mpegts
+// muxer untouched so latency/passphrase/streamid query parameters survive.
+pub(crate) fn create_stream_output_for_url(
+	url: &str,
+	protocol: crate::StreamProtocol,
+	whip_bearer_token: &str,
+	out_service: &mut *mut revo_lib::obs::obs_service,
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Result<*mut revo_lib::obs::obs_output, String> {
+	let url = url.trim();
+	match protocol {
+		crate::StreamProtocol::Whip => create_whip_stream_output(
+			url,
+			whip_bearer_token,
+			out_service,
+			out_video_encoder,
+			out_audio_encoder,
+			preference,
+			encoder_settings,
+		),
+		crate::StreamProtocol::Rtmp => {
+			let output = create_rtmp_stream_output(
+				url,
+				out_service,
+				out_video_encoder,
+				out_audio_encoder,
+				preference,
+				encoder_settings,
+			);
+			let output = if output.is_null() {
+				release_handed_over_encoders(out_video_encoder, out_audio_encoder);
+				create_ffmpeg_stream_output(url, protocol, encoder_settings)
+			} else {
+				output
+			};
+			if output.is_null() {
+				return Err("failed to create stream output".to_string());
+			}
+			Ok(output)
+		}
+		crate::StreamProtocol::Srt | crate::StreamProtocol::Rist => {
+			let output = create_mpegts_stream_output(
+				url,
+				out_service,
+				out_video_encoder,
+				out_audio_encoder,
+				preference,
+				encoder_settings,
+			);
+			let output = if output.is_null() {
+				release_handed_over_encoders(out_video_encoder, out_audio_encoder);
+				create_ffmpeg_stream_output(url, protocol, encoder_settings)
+			} else {
+				output
+			};
+			if output.is_null() {
+				return Err(format!(
+					"failed to create {} stream output",
+					protocol.label()
+				));
+			}
+			Ok(output)
+		}
+	}
+}
+
+// The raw ffmpeg_output fallback encodes on its own, so encoder references
+// handed over for sharing are dropped rather than left attached to nothing.
+fn release_handed_over_encoders(
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+) {
+	for encoder in [out_video_encoder, out_audio_encoder] {
+		if !encoder.is_null() {
+			unsafe { revo_lib::obs::obs_encoder_release(*encoder) };
+			*encoder = std::ptr::null_mut();
+		}
+	}
+}
+
+pub(crate) fn create_mpegts_stream_output(
+	url: &str,
+	out_service: &mut *mut revo_lib::obs::obs_service,
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_output {
+	if !output_type_available("ffmpeg_mpegts_muxer") {
+		return std::ptr::null_mut();
+	}
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if settings.is_null() {
+			return std::ptr::null_mut();
+		}
+
+		let server_key = std::ffi::CString::new("server").unwrap();
+		let server_val = std::ffi::CString::new(url).unwrap();
+		revo_lib::obs::obs_data_set_string(settings, server_key.as_ptr(), server_val.as_ptr());
+
+		let key_key = std::ffi::CString::new("key").unwrap();
+		let key_val = std::ffi::CString::new("").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, key_key.as_ptr(), key_val.as_ptr());
+
+		let service_id = std::ffi::CString::new("rtmp_custom").unwrap();
+		let service_name = std::ffi::CString::new("revo_service").unwrap();
+		let service = revo_lib::obs::obs_service_create(
+			service_id.as_ptr(),
+			service_name.as_ptr(),
+			settings,
+			std::ptr::null_mut(),
+		);
+		revo_lib::obs::obs_data_release(settings);
+		if service.is_null() {
+			return std::ptr::null_mut();
+		}
+
+		attach_service_output(
+			"ffmpeg_mpegts_muxer",
+			service,
+			out_service,
+			out_video_encoder,
+			out_audio_encoder,
+			preference,
+			encoder_settings,
+		)
+	}
+}
+
 pub(crate) fn create_rtmp_stream_output(
 	rtmp_url: &str,
 	out_service: &mut *mut revo_lib::obs::obs_service,
 	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
 	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
 	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
 ) -> *mut revo_lib::obs::obs_output {
 	unsafe {
 		let (server, key) = split_rtmp_url(rtmp_url);
@@ -372,7 +938,344 @@ pub(crate) fn create_rtmp_stream_output(
 			return std::ptr::null_mut();
 		}
 
-		let output_id = std::ffi::CString::new("rtmp_output").unwrap();
+		attach_service_output(
+			"rtmp_output",
+			service,
+			out_service,
+			out_video_encoder,
+			out_audio_encoder,
+			preference,
+			encoder_settings,
+		)
+	}
+}
+
+// Wraps a configured service in a new output. An encoder pair already placed in
+// `out_*_encoder` (references handed over by the caller) is attached as-is,
+// otherwise a fresh pair is created. Takes ownership of `service`; everything
+// created here is released on failure.
+unsafe fn attach_service_output(
+	output_id: &str,
+	service: *mut revo_lib::obs::obs_service,
+	out_service: &mut *mut revo_lib::obs::obs_service,
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_output {
+	let output_id = std::ffi::CString::new(output_id).unwrap();
+	let output_name = std::ffi::CString::new("revo_stream").unwrap();
+	let output = revo_lib::obs::obs_output_create(
+		output_id.as_ptr(),
+		output_name.as_ptr(),
+		std::ptr::null_mut(),
+		std::ptr::null_mut(),
+	);
+	if output.is_null() {
+		revo_lib::obs::obs_service_release(service);
+		return std::ptr::null_mut();
+	}
+
+	let reuse = !out_video_encoder.is_null() && !out_audio_encoder.is_null();
+	let (video_encoder, audio_encoder) = if reuse {
+		(*out_video_encoder, *out_audio_encoder)
+	} else {
+		(create_video_encoder(preference, encoder_settings), create_audio_encoder())
+	};
+	if video_encoder.is_null() || audio_encoder.is_null() {
+		if !video_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(video_encoder);
+		}
+		if !audio_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(audio_encoder);
+		}
+		revo_lib::obs::obs_output_release(output);
+		revo_lib::obs::obs_service_release(service);
+		return std::ptr::null_mut();
+	}
+
+	if !reuse {
+		revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
+		revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
+	}
+	revo_lib::obs::obs_output_set_video_encoder(output, video_encoder);
+	revo_lib::obs::obs_output_set_audio_encoder(output, audio_encoder, 0);
+	revo_lib::obs::obs_output_set_service(output, service);
+
+	*out_service = service;
+	*out_video_encoder = video_encoder;
+	*out_audio_encoder = audio_encoder;
+
+	output
+}
+
+// Catalog services resolve ingest specifics (RTMPS, per-service options)
+// through rtmp_common instead of a raw server/key pair.
+pub(crate) fn create_rtmp_common_stream_output(
+	service_name: &str,
+	server_url: &str,
+	key: &str,
+	out_service: &mut *mut revo_lib::obs::obs_service,
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Result<*mut revo_lib::obs::obs_output, String> {
+	if !service_type_available("rtmp_common") {
+		return Err("rtmp_common service is unavailable (rtmp-services module not loaded)".to_string());
+	}
+	let service_val = std::ffi::CString::new(service_name).map_err(|e| e.to_string())?;
+	let server_val = std::ffi::CString::new(server_url).map_err(|e| e.to_string())?;
+	let key_val = std::ffi::CString::new(key).map_err(|e| e.to_string())?;
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if settings.is_null() {
+			return Err("failed to allocate service settings".to_string());
+		}
+
+		let service_key = std::ffi::CString::new("service").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, service_key.as_ptr(), service_val.as_ptr());
+
+		let server_key = std::ffi::CString::new("server").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, server_key.as_ptr(), server_val.as_ptr());
+
+		let key_key = std::ffi::CString::new("key").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, key_key.as_ptr(), key_val.as_ptr());
+
+		let service_id = std::ffi::CString::new("rtmp_common").unwrap();
+		let service_obj_name = std::ffi::CString::new("revo_service").unwrap();
+		let service = revo_lib::obs::obs_service_create(
+			service_id.as_ptr(),
+			service_obj_name.as_ptr(),
+			settings,
+			std::ptr::null_mut(),
+		);
+		revo_lib::obs::obs_data_release(settings);
+		if service.is_null() {
+			return Err(format!("failed to create rtmp_common service for {service_name}"));
+		}
+
+		let output = attach_service_output(
+			"rtmp_output",
+			service,
+			out_service,
+			out_video_encoder,
+			out_audio_encoder,
+			preference,
+			encoder_settings,
+		);
+		if output.is_null() {
+			return Err(format!("failed to create stream output for {service_name}"));
+		}
+		Ok(output)
+	}
+}
+
+pub(crate) fn split_rtmp_url(rtmp_url: &str) -> (String, String) {
+	if let Some(idx) = rtmp_url.rfind('/') {
+		let (server, key) = rtmp_url.split_at(idx);
+		let key = key.trim_start_matches('/');
+		if server.is_empty() {
+			(rtmp_url.to_string(), String::new())
+		} else {
+			(server.to_string(), key.to_string())
+		}
+	} else {
+		(rtmp_url.to_string(), String::new())
+	}
+}
+
+pub(crate) fn output_type_available(id: &str) -> bool {
+	let mut idx: usize = 0;
+	unsafe {
+		loop {
+			let mut id_ptr: *const std::os::raw::c_char = std::ptr::null();
+			if !revo_lib::obs::obs_enum_output_types(
+				idx,
+				&mut id_ptr as *mut *const std::os::raw::c_char,
+			) {
+				break;
+			}
+			if !id_ptr.is_null() {
+				let value = std::ffi::CStr::from_ptr(id_ptr).to_string_lossy();
+				if value.eq_ignore_ascii_case(id) {
+					return true;
+				}
+			}
+			idx += 1;
+		}
+	}
+	false
+}
+
+pub(crate) fn service_type_available(id: &str) -> bool {
+	let mut idx: usize = 0;
+	unsafe {
+		loop {
+			let mut id_ptr: *const std::os::raw::c_char = std::ptr::null();
+			if !revo_lib::obs::obs_enum_service_types(
+				idx,
+				&mut id_ptr as *mut *const std::os::raw::c_char,
+			) {
+				break;
+			}
+			if !id_ptr.is_null() {
+				let value = std::ffi::CStr::from_ptr(id_ptr).to_string_lossy();
+				if value.eq_ignore_ascii_case(id) {
+					return true;
+				}
+			}
+			idx += 1;
+		}
+	}
+	false
+}
+
+// WebRTC peers negotiate H.264 constrained baseline without B-frames, so only
+// the bitrate, rate control and keyframe interval come from the streaming
+// settings; profile, B-frames and tune stay fixed.
+pub(crate) fn create_webrtc_video_encoder(
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> *mut revo_lib::obs::obs_encoder {
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if settings.is_null() {
+			return std::ptr::null_mut();
+		}
+		let bitrate_key = std::ffi::CString::new("bitrate").unwrap();
+		revo_lib::obs::obs_data_set_int(settings, bitrate_key.as_ptr(), encoder_settings.bitrate);
+
+		let rc_key = std::ffi::CString::new("rate_control").unwrap();
+		let rc_val = std::ffi::CString::new(encoder_settings.rate_control.as_str())
+			.unwrap_or_else(|_| std::ffi::CString::new("CBR").unwrap());
+		revo_lib::obs::obs_data_set_string(settings, rc_key.as_ptr(), rc_val.as_ptr());
+
+		// 0 ("auto") would leave late joiners waiting seconds for a keyframe.
+		let keyint_key = std::ffi::CString::new("keyint_sec").unwrap();
+		let keyint_sec = if encoder_settings.keyint_sec > 0 { encoder_settings.keyint_sec } else { 1 };
+		revo_lib::obs::obs_data_set_int(settings, keyint_key.as_ptr(), keyint_sec);
+
+		let bframes_key = std::ffi::CString::new("bframes").unwrap();
+		revo_lib::obs::obs_data_set_int(settings, bframes_key.as_ptr(), 0);
+
+		let bf_key = std::ffi::CString::new("bf").unwrap();
+		revo_lib::obs::obs_data_set_int(settings, bf_key.as_ptr(), 0);
+
+		let profile_key = std::ffi::CString::new("profile").unwrap();
+		let profile_val = std::ffi::CString::new("baseline").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, profile_key.as_ptr(), profile_val.as_ptr());
+
+		let tune_key = std::ffi::CString::new("tune").unwrap();
+		let tune_val = std::ffi::CString::new("zerolatency").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, tune_key.as_ptr(), tune_val.as_ptr());
+
+		let repeat_key = std::ffi::CString::new("repeat_headers").unwrap();
+		revo_lib::obs::obs_data_set_bool(settings, repeat_key.as_ptr(), true);
+
+		let mut candidates: Vec<&str> = Vec::new();
+		if matches!(preference, crate::VideoEncoderPreference::Hardware) {
+			candidates.extend(["h264_nvenc", "ffmpeg_nvenc", "h264_qsv", "h264_vaapi", "h264_amf"]);
+		}
+		candidates.extend(["obs_x264", "x264"]);
+
+		let mut enc = std::ptr::null_mut();
+		for encoder_id in candidates {
+			if !encoder_id_available(encoder_id) {
+				continue;
+			}
+			let id = std::ffi::CString::new(encoder_id).unwrap();
+			let name = std::ffi::CString::new("revo_video_webrtc").unwrap();
+			enc = revo_lib::obs::obs_video_encoder_create(
+				id.as_ptr(),
+				name.as_ptr(),
+				settings,
+				std::ptr::null_mut(),
+			);
+			if !enc.is_null() {
+				break;
+			}
+		}
+		revo_lib::obs::obs_data_release(settings);
+		enc
+	}
+}
+
+pub(crate) fn create_opus_encoder() -> *mut revo_lib::obs::obs_encoder {
+	if !encoder_id_available("ffmpeg_opus") {
+		return std::ptr::null_mut();
+	}
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if !settings.is_null() {
+			let key = std::ffi::CString::new("bitrate").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, key.as_ptr(), 128);
+		}
+		let id = std::ffi::CString::new("ffmpeg_opus").unwrap();
+		let name = std::ffi::CString::new("revo_audio_opus").unwrap();
+		let enc = revo_lib::obs::obs_audio_encoder_create(
+			id.as_ptr(),
+			name.as_ptr(),
+			settings,
+			0,
+			std::ptr::null_mut(),
+		);
+		if !settings.is_null() {
+			revo_lib::obs::obs_data_release(settings);
+		}
+		enc
+	}
+}
+
+pub(crate) fn create_whip_stream_output(
+	whip_url: &str,
+	bearer_token: &str,
+	out_service: &mut *mut revo_lib::obs::obs_service,
+	out_video_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	out_audio_encoder: &mut *mut revo_lib::obs::obs_encoder,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Result<*mut revo_lib::obs::obs_output, String> {
+	if !output_type_available("whip_output") || !service_type_available("whip_custom") {
+		return Err(
+			"WHIP output unavailable: the obs-webrtc module is not loaded (check the active plugin profile)"
+				.to_string(),
+		);
+	}
+
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if settings.is_null() {
+			return Err("failed to allocate WHIP service settings".to_string());
+		}
+
+		let server_key = std::ffi::CString::new("server").unwrap();
+		let server_val = std::ffi::CString::new(whip_url.trim())
+			.map_err(|_| "WHIP URL contains a NUL byte".to_string())?;
+		revo_lib::obs::obs_data_set_string(settings, server_key.as_ptr(), server_val.as_ptr());
+
+		let token = bearer_token.trim();
+		if !token.is_empty() {
+			let token_key = std::ffi::CString::new("bearer_token").unwrap();
+			let token_val = std::ffi::CString::new(token)
+				.map_err(|_| "WHIP bearer token contains a NUL byte".to_string())?;
+			revo_lib::obs::obs_data_set_string(settings, token_key.as_ptr(), token_val.as_ptr());
+		}
+
+		let service_id = std::ffi::CString::new("whip_custom").unwrap();
+		let service_name = std::ffi::CString::new("revo_whip_service").unwrap();
+		let service = revo_lib::obs::obs_service_create(
+			service_id.as_ptr(),
+			service_name.as_ptr(),
+			settings,
+			std::ptr::null_mut(),
+		);
+		revo_lib::obs::obs_data_release(settings);
+		if service.is_null() {
+			return Err("failed to create whip_custom service".to_string());
+		}
+
+		let output_id = std::ffi::CString::new("whip_output").unwrap();
 		let output_name = std::ffi::CString::new("revo_stream").unwrap();
 		let output = revo_lib::obs::obs_output_create(
 			output_id.as_ptr(),
@@ -382,12 +1285,25 @@ pub(crate) fn create_rtmp_stream_output(
 		);
 		if output.is_null() {
 			revo_lib::obs::obs_service_release(service);
-			return std::ptr::null_mut();
+			return Err("failed to create whip_output".to_string());
 		}
 
-		let video_encoder = create_video_encoder(preference);
-		let audio_encoder = create_audio_encoder();
+		// Same hand-over rule as attach_service_output.
+		let reuse = !out_video_encoder.is_null() && !out_audio_encoder.is_null();
+		let (video_encoder, audio_encoder) = if reuse {
+			(*out_video_encoder, *out_audio_encoder)
+		} else {
+			(
+				create_webrtc_video_encoder(preference, encoder_settings),
+				create_opus_encoder(),
+			)
+		};
 		if video_encoder.is_null() || audio_encoder.is_null() {
+			let missing = if video_encoder.is_null() {
+				"H.264"
+			} else {
+				"Opus (ffmpeg_opus)"
+			};
 			if !video_encoder.is_null() {
 				revo_lib::obs::obs_encoder_release(video_encoder);
 			}
@@ -396,11 +1312,13 @@ pub(crate) fn create_rtmp_stream_output(
 			}
 			revo_lib::obs::obs_output_release(output);
 			revo_lib::obs::obs_service_release(service);
-			return std::ptr::null_mut();
+			return Err(format!("WHIP requires an {missing} encoder, none could be created"));
 		}
 
-		revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
-		revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
+		if !reuse {
+			revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
+			revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
+		}
 		revo_lib::obs::obs_output_set_video_encoder(output, video_encoder);
 		revo_lib::obs::obs_output_set_audio_encoder(output, audio_encoder, 0);
 		revo_lib::obs::obs_output_set_service(output, service);
@@ -409,20 +1327,6 @@ pub(crate) fn create_rtmp_stream_output(
 		*out_video_encoder = video_encoder;
 		*out_audio_encoder = audio_encoder;
 
-		output
-	}
-}
-
-pub(crate) fn split_rtmp_url(rtmp_url: &str) -> (String, String) {
-	if let Some(idx) = rtmp_url.rfind('/') {
-		let (server, key) = rtmp_url.split_at(idx);
-		let key = key.trim_start_matches('/');
-		if server.is_empty() {
-			(rtmp_url.to_string(), String::new())
-		} else {
-			(server.to_string(), key.to_string())
-		}
-	} else {
-		(rtmp_url.to_string(), String::new())
+		Ok(output)
 	}
 }
@@ -0,0 +1,69 @@
+// Offscreen libobs startup for machines without a desktop session (render
+// boxes, CI). libobs-opengl only knows X11 and Wayland, so a virtual X server
+// (Xvfb) is still required; rendering goes through Mesa's llvmpipe and
+// everything that needs a real desktop is switched off.
+
+pub(crate) const OFFSCREEN_ENV: &str = "REVO_OFFSCREEN";
+
+// Modules that need X11/Wayland, on top of the capture modules init always
+// disables.
+pub(crate) const OFFSCREEN_DISABLED_MODULES: &[&str] = &["obs-browser"];
+
+// Source types backed by a window system; rejected with a clear error offscreen.
+const WINDOW_SOURCE_TYPES: [&str; 5] = [
+	"window_capture",
+	"xcomposite_input",
+	"xshm_input",
+	"pipewire-screen-capture-source",
+	"pipewire-window-capture-source",
+];
+
+static OFFSCREEN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn env_flag(name: &str) -> bool {
+	std::env::var(name)
+		.map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+		.unwrap_or(false)
+}
+
+pub(crate) fn offscreen_requested() -> bool {
+	env_flag(OFFSCREEN_ENV)
+}
+
+pub(crate) fn has_display() -> bool {
+	std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+pub(crate) fn force_software_gl() {
+	std::env::set_var("LIBGL_ALWAYS_SOFTWARE", "1");
+	std::env::set_var("MESA_LOADER_DRIVER_OVERRIDE", "llvmpipe");
+	std::env::set_var("GALLIUM_DRIVER", "llvmpipe");
+}
+
+/// Must run before `obs_startup`. Fails without an X server, since
+/// `obs_reset_video` cannot open a GL context otherwise.
+pub(crate) fn prepare_offscreen() -> Result<(), String> {
+	if std::env::var_os("DISPLAY").is_none() {
+		return Err(format!(
+			"{OFFSCREEN_ENV}=1 still needs an X server: run under `xvfb-run -a`, or start Xvfb and set DISPLAY."
+		));
+	}
+	force_software_gl();
+	OFFSCREEN.store(true, std::sync::atomic::Ordering::SeqCst);
+	Ok(())
+}
+
+pub(crate) fn is_offscreen() -> bool {
+	OFFSCREEN.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+pub(crate) fn reset() {
+	OFFSCREEN.store(false, std::sync::atomic::Ordering::SeqCst);
+}
+
+pub(crate) fn ensure_source_type_allowed(source_type: &str) -> Result<(), String> {
+	if is_offscreen() && WINDOW_SOURCE_TYPES.contains(&source_type.trim()) {
+		return Err(format!("{source_type} needs a desktop session and is unavailable offscreen"));
+	}
+	Ok(())
+}
@@ -5,25 +5,44 @@ pub(crate) fn cstr_to_string(ptr: *const i8) -> String {
 	unsafe { std::ffi::CStr::from_ptr(ptr).to_string_lossy().to_string() }
 }
 
-pub(crate) fn stop_recording_internal(runtime: &mut crate::ObsRuntime) {
+// Stops and releases the recording output and its encoders, leaving the
+// session bookkeeping (path, timers, segments) alone.
+pub(crate) fn release_record_output(runtime: &mut crate::ObsRuntime) {
 	unsafe {
 		if !runtime.output_record.is_null() {
+			super::record_split::detach_split_signal(runtime.output_record);
 			if let Ok(output) = revo_lib::recording::RecordingOutput::from_raw(runtime.output_record) {
 				output.stop();
 			}
+			super::output_state::detach_output_signals(super::output_state::RECORD_LABEL);
 			revo_lib::obs::obs_output_release(runtime.output_record);
 		}
 		if !runtime.record_video_encoder.is_null() {
 			revo_lib::obs::obs_encoder_release(runtime.record_video_encoder);
 		}
-		if !runtime.record_audio_encoder.is_null() {
-			revo_lib::obs::obs_encoder_release(runtime.record_audio_encoder);
+		for encoder in runtime.record_audio_encoders.drain(..) {
+			if !encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(encoder);
+			}
 		}
 	}
 	runtime.output_record = std::ptr::null_mut();
 	runtime.record_video_encoder = std::ptr::null_mut();
-	runtime.record_audio_encoder = std::ptr::null_mut();
+}
+
+// Returns every file the session produced (one per split segment).
+pub(crate) fn stop_recording_internal(runtime: &mut crate::ObsRuntime) -> Vec<std::path::PathBuf> {
+	release_record_output(runtime);
+	super::disk_watchdog::end_watch(super::output_state::RECORD_LABEL);
+	let (elapsed, _) = super::recording::recording_elapsed(runtime);
+	super::record_chapters::finish_session(elapsed.as_millis() as u64);
+	let segments = super::record_split::finish_session();
+	super::recording_history::finish_session(&segments, elapsed);
 	runtime.last_record_path = None;
+	runtime.record_started_at = None;
+	runtime.record_paused_at = None;
+	runtime.record_paused_total = std::time::Duration::ZERO;
+	segments
 }
 
 pub(crate) fn stop_streaming_internal(runtime: &mut crate::ObsRuntime) {
@@ -32,6 +51,7 @@ pub(crate) fn stop_streaming_internal(runtime: &mut crate::ObsRuntime) {
 			if let Ok(output) = revo_lib::streaming::StreamingOutput::from_raw(runtime.output_stream) {
 				output.stop();
 			}
+			super::output_state::detach_output_signals(super::output_state::STREAM_LABEL);
 			revo_lib::obs::obs_output_release(runtime.output_stream);
 		}
 		if !runtime.stream_service.is_null() {
@@ -48,32 +68,172 @@ pub(crate) fn stop_streaming_internal(runtime: &mut crate::ObsRuntime) {
 	runtime.stream_service = std::ptr::null_mut();
 	runtime.stream_video_encoder = std::ptr::null_mut();
 	runtime.stream_audio_encoder = std::ptr::null_mut();
+	runtime.stream_encoder_signature.clear();
+}
+
+fn obs_scale_type(scale_type: crate::settings::core::VideoScaleType) -> revo_lib::obs::obs_scale_type {
+	match scale_type {
+		crate::settings::core::VideoScaleType::Bilinear => {
+			revo_lib::obs::obs_scale_type_OBS_SCALE_BILINEAR
+		}
+		crate::settings::core::VideoScaleType::Bicubic => revo_lib::obs::obs_scale_type_OBS_SCALE_BICUBIC,
+		crate::settings::core::VideoScaleType::Lanczos => revo_lib::obs::obs_scale_type_OBS_SCALE_LANCZOS,
+		crate::settings::core::VideoScaleType::Area => revo_lib::obs::obs_scale_type_OBS_SCALE_AREA,
+	}
+}
+
+pub(crate) fn obs_video_format(
+	format: crate::settings::core::VideoColorFormat,
+) -> revo_lib::obs::video_format {
+	use crate::settings::core::VideoColorFormat;
+	match format {
+		VideoColorFormat::Nv12 => revo_lib::obs::video_format_VIDEO_FORMAT_NV12,
+		VideoColorFormat::I420 => revo_lib::obs::video_format_VIDEO_FORMAT_I420,
+		VideoColorFormat::I444 => revo_lib::obs::video_format_VIDEO_FORMAT_I444,
+		VideoColorFormat::P010 => revo_lib::obs::video_format_VIDEO_FORMAT_P010,
+		VideoColorFormat::I010 => revo_lib::obs::video_format_VIDEO_FORMAT_I010,
+		VideoColorFormat::P216 => revo_lib::obs::video_format_VIDEO_FORMAT_P216,
+		VideoColorFormat::P416 => revo_lib::obs::video_format_VIDEO_FORMAT_P416,
+		VideoColorFormat::Bgra => revo_lib::obs::video_format_VIDEO_FORMAT_BGRA,
+	}
+}
+
+pub(crate) fn color_format_from_obs(
+	format: revo_lib::obs::video_format,
+) -> Option<crate::settings::core::VideoColorFormat> {
+	crate::settings::core::VideoColorFormat::ALL
+		.into_iter()
+		.find(|f| obs_video_format(*f) == format)
+}
+
+pub(crate) fn obs_colorspace(
+	space: crate::settings::core::VideoColorSpace,
+) -> revo_lib::obs::video_colorspace {
+	use crate::settings::core::VideoColorSpace;
+	match space {
+		VideoColorSpace::Rec601 => revo_lib::obs::video_colorspace_VIDEO_CS_601,
+		VideoColorSpace::Rec709 => revo_lib::obs::video_colorspace_VIDEO_CS_709,
+		VideoColorSpace::Srgb => revo_lib::obs::video_colorspace_VIDEO_CS_SRGB,
+		VideoColorSpace::Rec2100Pq => revo_lib::obs::video_colorspace_VIDEO_CS_2100_PQ,
+		VideoColorSpace::Rec2100Hlg => revo_lib::obs::video_colorspace_VIDEO_CS_2100_HLG,
+	}
+}
+
+pub(crate) fn color_space_from_obs(
+	space: revo_lib::obs::video_colorspace,
+) -> Option<crate::settings::core::VideoColorSpace> {
+	// VIDEO_CS_DEFAULT is treated as 709 by libobs.
+	if space == revo_lib::obs::video_colorspace_VIDEO_CS_DEFAULT {
+		return Some(crate::settings::core::VideoColorSpace::Rec709);
+	}
+	crate::settings::core::VideoColorSpace::ALL
+		.into_iter()
+		.find(|c| obs_colorspace(*c) == space)
+}
+
+pub(crate) fn obs_video_range(
+	range: crate::settings::core::VideoColorRange,
+) -> revo_lib::obs::video_range_type {
+	match range {
+		crate::settings::core::VideoColorRange::Partial => {
+			revo_lib::obs::video_range_type_VIDEO_RANGE_PARTIAL
+		}
+		crate::settings::core::VideoColorRange::Full => revo_lib::obs::video_range_type_VIDEO_RANGE_FULL,
+	}
 }
 
-pub(crate) fn reset_video_with_resolution(width: u32, height: u32) -> bool {
+/// Resets video with a `width`x`height` canvas; output size, frame rate and
+/// downscale filter come from `video`.
+pub(crate) fn reset_video_with_resolution(
+	width: u32,
+	height: u32,
+	video: &crate::settings::core::VideoSettings,
+) -> bool {
 	unsafe {
 		let graphics_module = std::ffi::CString::new("libobs-opengl").unwrap();
 		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
 		ovi.graphics_module = graphics_module.as_ptr();
-		ovi.fps_num = 30;
-		ovi.fps_den = 1;
+		ovi.fps_num = video.fps_num;
+		ovi.fps_den = video.fps_den;
+		let (output_width, output_height) = video.output_for_canvas(width, height);
 		ovi.base_width = width;
 		ovi.base_height = height;
-		ovi.output_width = width;
-		ovi.output_height = height;
-		ovi.output_format = revo_lib::obs::video_format_VIDEO_FORMAT_NV12;
+		ovi.output_width = output_width;
+		ovi.output_height = output_height;
+		ovi.output_format = obs_video_format(video.color_format);
 		ovi.adapter = 0;
 		ovi.gpu_conversion = true;
-		ovi.colorspace = revo_lib::obs::video_colorspace_VIDEO_CS_709;
-		ovi.range = revo_lib::obs::video_range_type_VIDEO_RANGE_PARTIAL;
-		ovi.scale_type = revo_lib::obs::obs_scale_type_OBS_SCALE_BICUBIC;
+		ovi.colorspace = obs_colorspace(video.color_space);
+		ovi.range = obs_video_range(video.color_range);
+		ovi.scale_type = obs_scale_type(video.scale_type);
 
 		revo_lib::obs::obs_reset_video(&mut ovi as *mut _)
 			== revo_lib::obs::OBS_VIDEO_SUCCESS as i32
 	}
 }
 
-pub(crate) fn resolve_record_path(output_path: &str) -> Result<std::path::PathBuf, String> {
+pub(crate) fn obs_speaker_layout(
+	speakers: crate::settings::core::AudioSpeakerLayout,
+) -> revo_lib::obs::speaker_layout {
+	use crate::settings::core::AudioSpeakerLayout;
+	match speakers {
+		AudioSpeakerLayout::Mono => revo_lib::obs::speaker_layout_SPEAKERS_MONO,
+		AudioSpeakerLayout::Stereo => revo_lib::obs::speaker_layout_SPEAKERS_STEREO,
+		AudioSpeakerLayout::TwoPointOne => revo_lib::obs::speaker_layout_SPEAKERS_2POINT1,
+		AudioSpeakerLayout::Quad => revo_lib::obs::speaker_layout_SPEAKERS_4POINT0,
+		AudioSpeakerLayout::FourPointOne => revo_lib::obs::speaker_layout_SPEAKERS_4POINT1,
+		AudioSpeakerLayout::FivePointOne => revo_lib::obs::speaker_layout_SPEAKERS_5POINT1,
+		AudioSpeakerLayout::SevenPointOne => revo_lib::obs::speaker_layout_SPEAKERS_7POINT1,
+	}
+}
+
+pub(crate) fn speaker_layout_from_obs(
+	speakers: revo_lib::obs::speaker_layout,
+) -> Option<crate::settings::core::AudioSpeakerLayout> {
+	crate::settings::core::AudioSpeakerLayout::ALL
+		.into_iter()
+		.find(|l| obs_speaker_layout(*l) == speakers)
+}
+
+/// libobs refuses this while any output is still using audio.
+pub(crate) fn reset_audio(audio: &crate::settings::core::AudioEngineSettings) -> bool {
+	unsafe {
+		let mut oai: revo_lib::obs::obs_audio_info = std::mem::zeroed();
+		oai.samples_per_sec = audio.sample_rate;
+		oai.speakers = obs_speaker_layout(audio.speakers);
+		revo_lib::obs::obs_reset_audio(&oai as *const _)
+	}
+}
+
+pub(crate) fn resolve_record_path(
+	output_path: &str,
+	scene: Option<&str>,
+) -> Result<std::path::PathBuf, String> {
+	let path = build_record_path(output_path, scene)?;
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent).map_err(|e| format!("failed to create output dir: {e}"))?;
+	}
+	Ok(path)
+}
+
+// Points a requested record path at a container's extension; directories get
+// the default `record` stem.
+pub(crate) fn with_record_extension(output_path: &str, extension: &str) -> String {
+	let raw = output_path.trim();
+	let path = std::path::Path::new(raw);
+	let target = if raw.ends_with('/') || raw.ends_with('\\') || path.is_dir() {
+		path.join(format!("record.{extension}"))
+	} else {
+		path.with_extension(extension)
+	};
+	target.to_string_lossy().to_string()
+}
+
+// Same as resolve_record_path without touching the filesystem.
+pub(crate) fn build_record_path(
+	output_path: &str,
+	scene: Option<&str>,
+) -> Result<std::path::PathBuf, String> {
 	let raw_input = output_path.trim();
 	let mut base_path = std::path::PathBuf::from(raw_input);
 	if base_path.as_os_str().is_empty() {
@@ -91,8 +251,8 @@ pub(crate) fn resolve_record_path(output_path: &str) -> Result<std::path::PathBu
 
 	let looks_like_dir = raw_input.ends_with('/') || raw_input.ends_with('\\') || base_path.is_dir();
 
-	let mut path = if looks_like_dir {
-		base_path.join(format!("record_{unix_ts}.mp4"))
+	let (parent, stem, ext) = if looks_like_dir {
+		(base_path, "record".to_string(), "mp4".to_string())
 	} else {
 		let parent = base_path
 			.parent()
@@ -102,34 +262,52 @@ pub(crate) fn resolve_record_path(output_path: &str) -> Result<std::path::PathBu
 			.file_stem()
 			.and_then(|s| s.to_str())
 			.filter(|s| !s.trim().is_empty())
-			.unwrap_or("record");
+			.unwrap_or("record")
+			.to_string();
 		let ext = base_path
 			.extension()
 			.and_then(|e| e.to_str())
 			.filter(|e| !e.trim().is_empty())
-			.unwrap_or("mp4");
-		parent.join(format!("{stem}_{unix_ts}.{ext}"))
+			.unwrap_or("mp4")
+			.to_string();
+		(parent, stem, ext)
 	};
 
-	if let Some(parent) = path.parent() {
-		std::fs::create_dir_all(parent).map_err(|e| format!("failed to create output dir: {e}"))?;
+	// A template in the file name wins over the profile format; without either
+	// the legacy `{stem}_{unix_ts}` name is kept.
+	let naming = super::record_naming::record_naming_options();
+	let template = if super::record_naming::has_template_tokens(&stem) {
+		Some(stem.clone())
+	} else {
+		naming.format.clone()
+	};
+	let now = crate::utils::time::local_datetime_now();
+	let render = |counter: u32| -> std::path::PathBuf {
+		let name = match template.as_deref() {
+			Some(t) => super::record_naming::render_template(t, scene, &naming.profile, &now, counter),
+			None => format!("{stem}_{unix_ts}"),
+		};
+		let name = super::record_naming::sanitize_file_name(&name, naming.no_spaces);
+		parent.join(format!("{name}.{ext}"))
+	};
+
+	let mut path = render(1);
+	if template.as_deref().is_some_and(super::record_naming::uses_counter) {
+		for counter in 1..10_000 {
+			let candidate = render(counter);
+			if !candidate.exists() {
+				path = candidate;
+				break;
+			}
+		}
 	}
 
 	if path.exists() {
-		let parent = path
-			.parent()
-			.map(std::path::PathBuf::from)
-			.unwrap_or_else(std::path::PathBuf::new);
 		let stem = path
 			.file_stem()
 			.and_then(|s| s.to_str())
 			.unwrap_or("record")
 			.to_string();
-		let ext = path
-			.extension()
-			.and_then(|e| e.to_str())
-			.unwrap_or("mp4")
-			.to_string();
 
 		for idx in 1..1000 {
 			let candidate = parent.join(format!("{stem}_{idx}.{ext}"));
@@ -142,3 +320,29 @@ pub(crate) fn resolve_record_path(output_path: &str) -> Result<std::path::PathBu
 
 	Ok(path)
 }
+
+pub(crate) fn preview_record_path(
+	state: tauri::State<crate::ObsState>,
+	output_path: String,
+) -> Result<String, String> {
+	let scene = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?
+		.current_scene
+		.clone();
+	let settings = crate::settings::core::settings_get()?;
+	let output_path = match crate::settings::core::recording_custom_output_settings(&settings)? {
+		Some(custom) if custom.target == crate::settings::core::RecordingCustomTarget::Url => {
+			return Ok(custom.path.unwrap_or_default());
+		}
+		Some(custom) => {
+			with_record_extension(custom.path.as_deref().unwrap_or(&output_path), &custom.extension)
+		}
+		None => match crate::settings::core::recording_output_settings(&settings)?.container {
+			Some(container) => with_record_extension(&output_path, container.extension),
+			None => output_path,
+		},
+	};
+	build_record_path(&output_path, scene.as_deref()).map(|p| p.to_string_lossy().to_string())
+}
@@ -2,6 +2,8 @@ pub(crate) fn start(
 	state: tauri::State<crate::ObsState>,
 	root_dir: Option<String>,
 ) -> Result<String, String> {
+	// Resolved up front: a sequence stinger may need an ffmpeg render.
+	let transition = super::transitions::prepare_selected_transition();
 	let mut runtime = state
 		.runtime
 		.lock()
@@ -10,15 +12,19 @@ pub(crate) fn start(
 		return Ok("OBS already initialized".to_string());
 	}
 
-	let has_display = std::env::var_os("DISPLAY").is_some()
-		|| std::env::var_os("WAYLAND_DISPLAY").is_some();
-	if !has_display {
-		return Err("No display found (DISPLAY/WAYLAND_DISPLAY). Run under a desktop session or set DISPLAY.".to_string());
+	let offscreen = super::headless::offscreen_requested();
+	if !offscreen && !super::headless::has_display() {
+		return Err(format!(
+			"No display found (DISPLAY/WAYLAND_DISPLAY). Run under a desktop session, set DISPLAY, or set {}=1 to render offscreen.",
+			super::headless::OFFSCREEN_ENV
+		));
 	}
 
-	if std::env::var_os("REVO_FORCE_SOFTWARE_GL").is_some() {
-		std::env::set_var("LIBGL_ALWAYS_SOFTWARE", "1");
-		std::env::set_var("MESA_LOADER_DRIVER_OVERRIDE", "llvmpipe");
+	super::headless::reset();
+	if offscreen {
+		super::headless::prepare_offscreen()?;
+	} else if std::env::var_os("REVO_FORCE_SOFTWARE_GL").is_some() {
+		super::headless::force_software_gl();
 	}
 
 	if let Ok(settings) = crate::settings::core::settings_get() {
@@ -215,8 +221,13 @@ pub(crate) fn start(
 			"linux-pipewire",
 			"frontend-tools",
 		];
-		for name in disabled {
-			let module = std::ffi::CString::new(name).map_err(|_| "module name".to_string())?;
+		let offscreen_disabled: &[&str] = if offscreen {
+			super::headless::OFFSCREEN_DISABLED_MODULES
+		} else {
+			&[]
+		};
+		for name in disabled.iter().chain(offscreen_disabled) {
+			let module = std::ffi::CString::new(*name).map_err(|_| "module name".to_string())?;
 			revo_lib::obs::obs_add_disabled_module(module.as_ptr());
 		}
 
@@ -243,10 +254,17 @@ pub(crate) fn start(
 	}
 
 	if !reset_video_audio() {
+		if offscreen {
+			return Err(
+				"obs_reset_video/obs_reset_audio failed (offscreen mode needs Xvfb and Mesa llvmpipe)"
+					.to_string(),
+			);
+		}
 		return Err("obs_reset_video/obs_reset_audio failed".to_string());
 	}
 
 	ensure_scene(&mut runtime, &root)?;
+	super::transitions::ensure_program_transition(&mut runtime, transition);
 
 	runtime.initialized = true;
 	revo_lib::runtime::set_initialized(true);
@@ -263,6 +281,8 @@ pub(crate) fn shutdown(state: tauri::State<crate::ObsState>) -> Result<String, S
 	}
 	super::helpers::stop_recording_internal(&mut runtime);
 	super::helpers::stop_streaming_internal(&mut runtime);
+	super::stream_targets::stop_all_stream_targets_internal(&mut runtime);
+	super::replay_buffer::stop_replay_buffer_internal(&mut runtime);
 	cleanup_scene(&mut runtime);
 	unsafe {
 		revo_lib::obs::obs_shutdown();
@@ -360,6 +380,9 @@ pub(crate) fn cleanup_scene(runtime: &mut crate::ObsRuntime) {
 			revo_lib::obs::obs_view_destroy(runtime.preview_view);
 		}
 		revo_lib::obs::obs_set_output_source(0, std::ptr::null_mut());
+	}
+	super::transitions::release_program_transition(runtime);
+	unsafe {
 		for state in runtime.scenes.values() {
 			if !state.scene_source.is_null() {
 				revo_lib::obs::obs_source_dec_showing(state.scene_source);
@@ -384,33 +407,30 @@ pub(crate) fn cleanup_scene(runtime: &mut crate::ObsRuntime) {
 }
 
 pub(crate) fn reset_video_audio() -> bool {
-	unsafe {
-		let graphics_module = std::ffi::CString::new("libobs-opengl").unwrap();
-		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
-		ovi.graphics_module = graphics_module.as_ptr();
-		ovi.fps_num = 30;
-		ovi.fps_den = 1;
-		ovi.base_width = 1280;
-		ovi.base_height = 720;
-		ovi.output_width = 1280;
-		ovi.output_height = 720;
-		ovi.output_format = revo_lib::obs::video_format_VIDEO_FORMAT_NV12;
-		ovi.adapter = 0;
-		ovi.gpu_conversion = true;
-		ovi.colorspace = revo_lib::obs::video_colorspace_VIDEO_CS_709;
-		ovi.range = revo_lib::obs::video_range_type_VIDEO_RANGE_PARTIAL;
-		ovi.scale_type = revo_lib::obs::obs_scale_type_OBS_SCALE_BICUBIC;
-
-		let video_ok = revo_lib::obs::obs_reset_video(&mut ovi as *mut _)
-			== revo_lib::obs::OBS_VIDEO_SUCCESS as i32;
-
-		let mut oai: revo_lib::obs::obs_audio_info = std::mem::zeroed();
-		oai.samples_per_sec = 48_000;
-		oai.speakers = revo_lib::obs::speaker_layout_SPEAKERS_STEREO;
-		let audio_ok = revo_lib::obs::obs_reset_audio(&oai as *const _);
-
-		video_ok && audio_ok
-	}
+	// A broken profile must not keep OBS from starting; fall back to defaults.
+	let settings = crate::settings::core::settings_get().ok();
+	let video = settings
+		.as_ref()
+		.and_then(|s| crate::settings::core::video_settings(s).ok())
+		.unwrap_or_default();
+	let (width, height) = video
+		.canvas
+		.or_else(|| {
+			settings
+				.as_ref()
+				.and_then(|s| s.scene_resolution.as_deref())
+				.and_then(crate::settings::core::parse_resolution)
+		})
+		.unwrap_or((1280, 720));
+	let video_ok = crate::runtime::helpers::reset_video_with_resolution(width, height, &video);
+
+	let audio = settings
+		.as_ref()
+		.and_then(|s| crate::settings::core::audio_engine_settings(s).ok())
+		.unwrap_or_default();
+	let audio_ok = crate::runtime::helpers::reset_audio(&audio);
+
+	video_ok && audio_ok
 }
 
 fn ensure_libobs_effects(
@@ -1,9 +1,22 @@
 #![allow(dead_code)]
 
 pub mod init;
+pub mod headless;
 pub mod state;
 pub mod encoders;
 pub mod preview;
 pub mod recording;
+pub mod replay_buffer;
+pub mod record_split;
+pub mod record_naming;
+pub mod record_chapters;
+pub mod recording_history;
+pub mod remux;
+pub mod disk_watchdog;
 pub mod streaming;
+pub mod stream_targets;
 pub mod helpers;
+pub mod output_state;
+pub mod stats;
+pub mod transitions;
+pub mod stinger;
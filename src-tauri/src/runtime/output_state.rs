@@ -0,0 +1,354 @@
+// Per-output lifecycle tracking driven by libobs output signals.
+
+pub(crate) const OUTPUT_STATE_EVENT: &str = "obs-output-state";
+pub(crate) const STREAM_LABEL: &str = "stream";
+pub(crate) const RECORD_LABEL: &str = "record";
+pub(crate) const REPLAY_LABEL: &str = "replay";
+
+pub(crate) fn stream_target_label(name: &str) -> String {
+	format!("target:{name}")
+}
+
+static APP_HANDLE: std::sync::OnceLock<tauri::AppHandle> = std::sync::OnceLock::new();
+static OUTPUT_STATES: std::sync::OnceLock<
+	std::sync::Mutex<std::collections::HashMap<String, crate::OutputStateInfo>>,
+> = std::sync::OnceLock::new();
+// label -> (output pointer, boxed label handed to libobs as callback data)
+static SIGNAL_BINDINGS: std::sync::OnceLock<
+	std::sync::Mutex<std::collections::HashMap<String, (usize, usize)>>,
+> = std::sync::OnceLock::new();
+
+const TRACKED_SIGNALS: [&str; 4] = ["start", "stop", "reconnect", "reconnect_success"];
+
+fn output_states() -> &'static std::sync::Mutex<std::collections::HashMap<String, crate::OutputStateInfo>> {
+	OUTPUT_STATES.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+fn signal_bindings() -> &'static std::sync::Mutex<std::collections::HashMap<String, (usize, usize)>> {
+	SIGNAL_BINDINGS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+pub(crate) fn set_app_handle(handle: tauri::AppHandle) {
+	let _ = APP_HANDLE.set(handle);
+}
+
+pub(crate) fn app_handle() -> Option<&'static tauri::AppHandle> {
+	APP_HANDLE.get()
+}
+
+pub(crate) fn now_ms() -> u64 {
+	std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|d| d.as_millis() as u64)
+		.unwrap_or(0)
+}
+
+fn update_state<F>(label: &str, apply: F)
+where
+	F: FnOnce(&mut crate::OutputStateInfo),
+{
+	let snapshot = {
+		let Ok(mut states) = output_states().lock() else {
+			return;
+		};
+		let entry = states
+			.entry(label.to_string())
+			.or_insert_with(|| crate::OutputStateInfo {
+				label: label.to_string(),
+				phase: crate::OutputPhase::Stopped,
+				last_error_code: None,
+				last_error: None,
+				reconnect_attempts: 0,
+				reconnect_timeout_sec: None,
+				updated_ms: 0,
+			});
+		apply(entry);
+		entry.updated_ms = now_ms();
+		entry.clone()
+	};
+
+	crate::logging::debug::push_debug_log_entry(
+		format!("output_state:{}", snapshot.label),
+		serde_json::to_value(&snapshot).ok(),
+	);
+	if let Some(handle) = app_handle() {
+		use tauri::Emitter;
+		let _ = handle.emit(OUTPUT_STATE_EVENT, &snapshot);
+	}
+}
+
+pub(crate) fn output_state(label: &str) -> Option<crate::OutputStateInfo> {
+	output_states().lock().ok().and_then(|s| s.get(label).cloned())
+}
+
+pub(crate) fn output_failed(label: &str) -> bool {
+	output_state(label).is_some_and(|s| s.phase == crate::OutputPhase::Failed)
+}
+
+pub(crate) fn list_output_states() -> Result<Vec<crate::OutputStateInfo>, String> {
+	let states = output_states()
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	let mut list: Vec<crate::OutputStateInfo> = states.values().cloned().collect();
+	list.sort_by(|a, b| a.label.cmp(&b.label));
+	Ok(list)
+}
+
+pub(crate) fn configure_stream_reconnect(
+	output: *mut revo_lib::obs::obs_output,
+	reconnect: crate::settings::core::StreamReconnectSettings,
+) {
+	if output.is_null() {
+		return;
+	}
+	unsafe {
+		revo_lib::obs::obs_output_set_reconnect_settings(
+			output,
+			reconnect.retries,
+			reconnect.delay_sec,
+		);
+	}
+}
+
+// A stream that ends on an error (reconnects exhausted, connect failure) would
+// otherwise keep its output and encoders until the user pressed stop, and the
+// next start would report it as still active. The release runs on its own
+// thread: the stop signal can fire while a caller holds the runtime lock.
+fn release_failed_output(label: &str, output: *mut revo_lib::obs::obs_output) {
+	let Some(handle) = app_handle().cloned() else {
+		return;
+	};
+	let label = label.to_string();
+	let output = output as usize;
+	std::thread::spawn(move || {
+		use tauri::Manager;
+		let state = handle.state::<crate::ObsState>();
+		let Ok(mut runtime) = state.runtime.lock() else {
+			return;
+		};
+		if label == STREAM_LABEL {
+			if runtime.output_stream as usize == output {
+				super::helpers::stop_streaming_internal(&mut runtime);
+			}
+		} else if let Some(name) = label.strip_prefix("target:") {
+			let current = runtime.stream_targets.get(name).map(|t| t.output as usize);
+			if current == Some(output) {
+				super::stream_targets::stop_stream_target_internal(&mut runtime, name);
+			}
+		}
+	});
+}
+
+unsafe fn calldata_i64(cd: *mut revo_lib::obs::calldata_t, name: &str) -> Option<i64> {
+	if cd.is_null() {
+		return None;
+	}
+	let key = std::ffi::CString::new(name).ok()?;
+	let mut value: std::os::raw::c_longlong = 0;
+	let found = revo_lib::obs::calldata_get_data(
+		cd,
+		key.as_ptr(),
+		&mut value as *mut _ as *mut std::os::raw::c_void,
+		std::mem::size_of::<std::os::raw::c_longlong>(),
+	);
+	if found {
+		Some(value as i64)
+	} else {
+		None
+	}
+}
+
+unsafe fn callback_label<'a>(data: *mut std::os::raw::c_void) -> Option<&'a str> {
+	if data.is_null() {
+		return None;
+	}
+	Some((*(data as *const String)).as_str())
+}
+
+unsafe extern "C" fn output_start_cb(
+	data: *mut std::os::raw::c_void,
+	_cd: *mut revo_lib::obs::calldata_t,
+) {
+	if let Some(label) = callback_label(data) {
+		update_state(label, |s| {
+			s.phase = crate::OutputPhase::Live;
+			s.last_error_code = None;
+			s.last_error = None;
+			s.reconnect_attempts = 0;
+			s.reconnect_timeout_sec = None;
+		});
+	}
+}
+
+unsafe extern "C" fn output_reconnect_cb(
+	data: *mut std::os::raw::c_void,
+	cd: *mut revo_lib::obs::calldata_t,
+) {
+	if let Some(label) = callback_label(data) {
+		let timeout_sec = calldata_i64(cd, "timeout_sec");
+		update_state(label, |s| {
+			s.phase = crate::OutputPhase::Reconnecting;
+			s.reconnect_attempts = s.reconnect_attempts.saturating_add(1);
+			s.reconnect_timeout_sec = timeout_sec;
+		});
+	}
+}
+
+unsafe extern "C" fn output_reconnect_success_cb(
+	data: *mut std::os::raw::c_void,
+	_cd: *mut revo_lib::obs::calldata_t,
+) {
+	if let Some(label) = callback_label(data) {
+		update_state(label, |s| {
+			s.phase = crate::OutputPhase::Live;
+			s.reconnect_timeout_sec = None;
+		});
+	}
+}
+
+unsafe extern "C" fn output_stop_cb(
+	data: *mut std::os::raw::c_void,
+	cd: *mut revo_lib::obs::calldata_t,
+) {
+	let Some(label) = callback_label(data) else {
+		return;
+	};
+	let code = calldata_i64(cd, "code").unwrap_or(revo_lib::obs::OBS_OUTPUT_SUCCESS as i64);
+	let mut output: *mut revo_lib::obs::obs_output = std::ptr::null_mut();
+	if !cd.is_null() {
+		let key = std::ffi::CString::new("output").unwrap();
+		revo_lib::obs::calldata_get_data(
+			cd,
+			key.as_ptr(),
+			&mut output as *mut _ as *mut std::os::raw::c_void,
+			std::mem::size_of::<*mut revo_lib::obs::obs_output>(),
+		);
+	}
+	let last_error = if output.is_null() {
+		String::new()
+	} else {
+		super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
+	};
+	let failed = code != revo_lib::obs::OBS_OUTPUT_SUCCESS as i64;
+	update_state(label, |s| {
+		s.reconnect_timeout_sec = None;
+		if !failed {
+			s.phase = crate::OutputPhase::Stopped;
+		} else {
+			s.phase = crate::OutputPhase::Failed;
+			s.last_error_code = Some(code);
+			s.last_error = if last_error.is_empty() {
+				Some(output_error_label(code).to_string())
+			} else {
+				Some(last_error)
+			};
+		}
+	});
+	if failed && !output.is_null() {
+		release_failed_output(label, output);
+	}
+}
+
+pub(crate) fn output_error_label(code: i64) -> &'static str {
+	match code {
+		x if x == revo_lib::obs::OBS_OUTPUT_BAD_PATH as i64 => "bad path",
+		x if x == revo_lib::obs::OBS_OUTPUT_CONNECT_FAILED as i64 => "connect failed",
+		x if x == revo_lib::obs::OBS_OUTPUT_INVALID_STREAM as i64 => "invalid stream",
+		x if x == revo_lib::obs::OBS_OUTPUT_ERROR as i64 => "output error",
+		x if x == revo_lib::obs::OBS_OUTPUT_DISCONNECTED as i64 => "disconnected",
+		x if x == revo_lib::obs::OBS_OUTPUT_UNSUPPORTED as i64 => "unsupported",
+		x if x == revo_lib::obs::OBS_OUTPUT_NO_SPACE as i64 => "no space left",
+		x if x == revo_lib::obs::OBS_OUTPUT_ENCODE_ERROR as i64 => "encode error",
+		_ => "unknown error",
+	}
+}
+
+fn signal_callback(
+	signal: &str,
+) -> unsafe extern "C" fn(*mut std::os::raw::c_void, *mut revo_lib::obs::calldata_t) {
+	match signal {
+		"start" => output_start_cb,
+		"reconnect" => output_reconnect_cb,
+		"reconnect_success" => output_reconnect_success_cb,
+		_ => output_stop_cb,
+	}
+}
+
+// Must run before the output is started so the "start" signal is observed.
+pub(crate) fn attach_output_signals(label: &str, output: *mut revo_lib::obs::obs_output) {
+	if output.is_null() {
+		return;
+	}
+	detach_output_signals(label);
+	update_state(label, |s| {
+		s.phase = crate::OutputPhase::Starting;
+		s.last_error_code = None;
+		s.last_error = None;
+		s.reconnect_attempts = 0;
+		s.reconnect_timeout_sec = None;
+	});
+
+	let data = Box::into_raw(Box::new(label.to_string()));
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_signal_handler(output);
+		if handler.is_null() {
+			drop(Box::from_raw(data));
+			return;
+		}
+		for signal in TRACKED_SIGNALS {
+			let signal_c = std::ffi::CString::new(signal).unwrap();
+			revo_lib::obs::signal_handler_connect(
+				handler,
+				signal_c.as_ptr(),
+				Some(signal_callback(signal)),
+				data as *mut std::os::raw::c_void,
+			);
+		}
+	}
+	if let Ok(mut bindings) = signal_bindings().lock() {
+		bindings.insert(label.to_string(), (output as usize, data as usize));
+	}
+}
+
+// Must run before the output is released.
+pub(crate) fn detach_output_signals(label: &str) {
+	let binding = signal_bindings()
+		.lock()
+		.ok()
+		.and_then(|mut b| b.remove(label));
+	let Some((output, data)) = binding else {
+		return;
+	};
+	let output = output as *mut revo_lib::obs::obs_output;
+	let data = data as *mut String;
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_signal_handler(output);
+		if !handler.is_null() {
+			for signal in TRACKED_SIGNALS {
+				let signal_c = std::ffi::CString::new(signal).unwrap();
+				revo_lib::obs::signal_handler_disconnect(
+					handler,
+					signal_c.as_ptr(),
+					Some(signal_callback(signal)),
+					data as *mut std::os::raw::c_void,
+				);
+			}
+		}
+		drop(Box::from_raw(data));
+	}
+	update_state(label, |s| {
+		if s.phase != crate::OutputPhase::Failed {
+			s.phase = crate::OutputPhase::Stopped;
+		}
+		s.reconnect_timeout_sec = None;
+	});
+}
+
+pub(crate) fn mark_start_failed(label: &str, message: &str) {
+	detach_output_signals(label);
+	let message = message.to_string();
+	update_state(label, |s| {
+		s.phase = crate::OutputPhase::Failed;
+		s.last_error = Some(message);
+	});
+}
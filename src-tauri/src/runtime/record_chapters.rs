@@ -0,0 +1,232 @@
+// Recording chapter markers: the muxer's `add_chapter` proc where the output
+// has one, otherwise an ffmetadata/WebVTT sidecar next to the recording.
+
+struct ChapterSession {
+	chapters: Vec<crate::RecordingChapter>,
+	sidecar: Option<std::path::PathBuf>,
+	format: crate::settings::core::ChapterSidecarFormat,
+}
+
+static CHAPTER_SESSION: std::sync::OnceLock<std::sync::Mutex<Option<ChapterSession>>> =
+	std::sync::OnceLock::new();
+
+fn chapter_session() -> &'static std::sync::Mutex<Option<ChapterSession>> {
+	CHAPTER_SESSION.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+fn add_native_chapter(output: *mut revo_lib::obs::obs_output, name: &str) -> bool {
+	let Ok(name_c) = std::ffi::CString::new(name) else {
+		return false;
+	};
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_proc_handler(output);
+		if handler.is_null() {
+			return false;
+		}
+		let proc_name = std::ffi::CString::new("add_chapter").unwrap();
+		let key = std::ffi::CString::new("chapter_name").unwrap();
+		let mut cd: revo_lib::obs::calldata_t = std::mem::zeroed();
+		revo_lib::obs::calldata_set_string(&mut cd, key.as_ptr(), name_c.as_ptr());
+		let called = revo_lib::obs::proc_handler_call(handler, proc_name.as_ptr(), &mut cd);
+		if !cd.fixed && !cd.stack.is_null() {
+			revo_lib::obs::bfree(cd.stack as *mut std::os::raw::c_void);
+		}
+		called
+	}
+}
+
+fn sidecar_path(
+	recording: &std::path::Path,
+	format: crate::settings::core::ChapterSidecarFormat,
+) -> std::path::PathBuf {
+	match format {
+		crate::settings::core::ChapterSidecarFormat::FfMetadata => {
+			recording.with_extension("chapters.txt")
+		}
+		crate::settings::core::ChapterSidecarFormat::WebVtt => {
+			recording.with_extension("chapters.vtt")
+		}
+	}
+}
+
+fn vtt_timestamp(ms: u64) -> String {
+	format!(
+		"{:02}:{:02}:{:02}.{:03}",
+		ms / 3_600_000,
+		(ms / 60_000) % 60,
+		(ms / 1000) % 60,
+		ms % 1000
+	)
+}
+
+fn escape_ffmetadata(value: &str) -> String {
+	let mut out = String::with_capacity(value.len());
+	for ch in value.chars() {
+		if matches!(ch, '=' | ';' | '#' | '\\' | '\n') {
+			out.push('\\');
+		}
+		out.push(ch);
+	}
+	out
+}
+
+// Each chapter ends where the next begins; the last one ends at `end_ms`.
+fn render_sidecar(
+	chapters: &[&crate::RecordingChapter],
+	format: crate::settings::core::ChapterSidecarFormat,
+	end_ms: u64,
+) -> String {
+	let mut out = String::new();
+	match format {
+		crate::settings::core::ChapterSidecarFormat::FfMetadata => out.push_str(";FFMETADATA1\n"),
+		crate::settings::core::ChapterSidecarFormat::WebVtt => out.push_str("WEBVTT\n"),
+	}
+	for (idx, chapter) in chapters.iter().enumerate() {
+		let end = chapters
+			.get(idx + 1)
+			.map(|next| next.offset_ms)
+			.unwrap_or(end_ms)
+			.max(chapter.offset_ms);
+		match format {
+			crate::settings::core::ChapterSidecarFormat::FfMetadata => out.push_str(&format!(
+				"\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={end}\ntitle={}\n",
+				chapter.offset_ms,
+				escape_ffmetadata(&chapter.name)
+			)),
+			crate::settings::core::ChapterSidecarFormat::WebVtt => out.push_str(&format!(
+				"\n{}\n{} --> {}\n{}\n",
+				idx + 1,
+				vtt_timestamp(chapter.offset_ms),
+				vtt_timestamp(end),
+				chapter.name.replace("-->", "->")
+			)),
+		}
+	}
+	out
+}
+
+fn write_sidecar(session: &ChapterSession, end_ms: u64) -> Result<(), String> {
+	let Some(path) = session.sidecar.as_ref() else {
+		return Ok(());
+	};
+	let chapters: Vec<&crate::RecordingChapter> =
+		session.chapters.iter().filter(|c| !c.native).collect();
+	std::fs::write(path, render_sidecar(&chapters, session.format, end_ms))
+		.map_err(|e| format!("failed to write chapter file: {e}"))
+}
+
+pub(crate) fn add_chapter(
+	runtime: &crate::ObsRuntime,
+	name: Option<String>,
+) -> Result<crate::RecordingChapter, String> {
+	if runtime.output_record.is_null() {
+		return Err("Recording not active".to_string());
+	}
+	let offset_ms = super::recording::recording_elapsed(runtime).0.as_millis() as u64;
+	let mut guard = chapter_session()
+		.lock()
+		.map_err(|_| "chapter state poisoned".to_string())?;
+	if guard.is_none() {
+		let settings = crate::settings::core::settings_get()?;
+		let config = crate::settings::core::recording_chapter_settings(&settings)?;
+		*guard = Some(ChapterSession {
+			chapters: Vec::new(),
+			sidecar: None,
+			format: config.sidecar_format,
+		});
+	}
+	let Some(session) = guard.as_mut() else {
+		return Err("chapter state unavailable".to_string());
+	};
+
+	let index = session.chapters.len() + 1;
+	let name = name
+		.map(|n| n.trim().to_string())
+		.filter(|n| !n.is_empty())
+		.unwrap_or_else(|| format!("Chapter {index}"));
+	let native = add_native_chapter(runtime.output_record, &name);
+	let sidecar = if native {
+		None
+	} else {
+		// One sidecar per session, next to the file that was open at the first
+		// chapter; offsets stay relative to the recording start across splits.
+		if session.sidecar.is_none() {
+			let recording = runtime
+				.last_record_path
+				.as_ref()
+				.filter(|p| p.parent().is_some_and(|dir| dir.is_dir()))
+				.ok_or_else(|| "Chapters need a recording written to a file".to_string())?;
+			session.sidecar = Some(sidecar_path(recording, session.format));
+		}
+		session.sidecar.clone()
+	};
+
+	let chapter = crate::RecordingChapter {
+		index,
+		name,
+		offset_ms,
+		native,
+		sidecar: sidecar.map(|p| p.to_string_lossy().to_string()),
+	};
+	session.chapters.push(chapter.clone());
+	if !native {
+		write_sidecar(session, offset_ms)?;
+	}
+	Ok(chapter)
+}
+
+pub(crate) fn add_recording_chapter(
+	state: tauri::State<crate::ObsState>,
+	name: Option<String>,
+) -> Result<crate::RecordingChapter, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	add_chapter(&runtime, name)
+}
+
+/// Adds a chapter named after the new scene when the profile asks for it.
+pub(crate) fn scene_switched(runtime: &crate::ObsRuntime, scene: &str) {
+	if runtime.output_record.is_null() {
+		return;
+	}
+	let enabled = crate::settings::core::settings_get()
+		.ok()
+		.and_then(|s| crate::settings::core::recording_chapter_settings(&s).ok())
+		.is_some_and(|c| c.on_scene_switch);
+	if !enabled {
+		return;
+	}
+	if let Err(err) = add_chapter(runtime, Some(scene.to_string())) {
+		crate::logging::debug::push_debug_log_entry(
+			"recording_chapter_failed".to_string(),
+			Some(serde_json::json!({ "scene": scene, "error": err })),
+		);
+	}
+}
+
+pub(crate) fn session_chapters() -> Vec<crate::RecordingChapter> {
+	chapter_session()
+		.lock()
+		.ok()
+		.and_then(|g| g.as_ref().map(|s| s.chapters.clone()))
+		.unwrap_or_default()
+}
+
+/// Closes the last sidecar chapter at the final recording length.
+pub(crate) fn finish_session(end_ms: u64) {
+	let session = chapter_session().lock().ok().and_then(|mut g| g.take());
+	let Some(session) = session else {
+		return;
+	};
+	if let Err(err) = write_sidecar(&session, end_ms) {
+		crate::logging::debug::push_debug_log_entry(
+			"recording_chapter_failed".to_string(),
+			Some(serde_json::json!({ "error": err })),
+		);
+	}
+}
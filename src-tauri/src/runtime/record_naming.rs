@@ -0,0 +1,96 @@
+// Recording file-name templates: OBS-style date tokens plus {scene}/{profile}/{counter}.
+
+pub(crate) struct RecordNamingOptions {
+	pub(crate) format: Option<String>,
+	pub(crate) no_spaces: bool,
+	pub(crate) profile: String,
+}
+
+/// Reads `recordFilenameFormat` / `recordFilenameNoSpaces` from the active profile.
+pub(crate) fn record_naming_options() -> RecordNamingOptions {
+	let settings = crate::settings::core::settings_get().ok();
+	let section = settings
+		.as_ref()
+		.and_then(|s| crate::settings::core::ui_profile_section(s, &["output", "recording"]));
+	RecordNamingOptions {
+		format: crate::settings::core::profile_field(section, "recordFilenameFormat"),
+		// Same default as the settings toggle.
+		no_spaces: crate::settings::core::profile_bool(section, "recordFilenameNoSpaces")
+			.unwrap_or(true),
+		profile: settings
+			.and_then(|s| s.active_profile)
+			.unwrap_or_else(|| "default".to_string()),
+	}
+}
+
+pub(crate) fn has_template_tokens(name: &str) -> bool {
+	name.contains('%') || (name.contains('{') && name.contains('}'))
+}
+
+pub(crate) fn uses_counter(template: &str) -> bool {
+	template.contains("{counter}")
+}
+
+pub(crate) fn render_template(
+	template: &str,
+	scene: Option<&str>,
+	profile: &str,
+	now: &crate::utils::time::LocalDateTime,
+	counter: u32,
+) -> String {
+	let mut out = String::with_capacity(template.len() + 16);
+	let mut rest = template;
+	while let Some(ch) = rest.chars().next() {
+		if ch == '%' {
+			let replacements: [(&str, String); 8] = [
+				("%CCYY", format!("{:04}", now.year)),
+				("%YY", format!("{:02}", now.year.rem_euclid(100))),
+				("%MM", format!("{:02}", now.month)),
+				("%DD", format!("{:02}", now.day)),
+				("%hh", format!("{:02}", now.hour)),
+				("%mm", format!("{:02}", now.minute)),
+				("%ss", format!("{:02}", now.second)),
+				("%%", "%".to_string()),
+			];
+			if let Some((token, value)) = replacements.iter().find(|(t, _)| rest.starts_with(t)) {
+				out.push_str(value);
+				rest = &rest[token.len()..];
+				continue;
+			}
+		} else if ch == '{' {
+			let replacements: [(&str, String); 3] = [
+				("{scene}", scene.unwrap_or("scene").to_string()),
+				("{profile}", profile.to_string()),
+				("{counter}", format!("{counter:03}")),
+			];
+			if let Some((token, value)) = replacements.iter().find(|(t, _)| rest.starts_with(t)) {
+				out.push_str(value);
+				rest = &rest[token.len()..];
+				continue;
+			}
+		}
+		out.push(ch);
+		rest = &rest[ch.len_utf8()..];
+	}
+	out
+}
+
+/// Replaces characters that are illegal on common filesystems; path separators
+/// coming from scene/profile names are treated as illegal too.
+pub(crate) fn sanitize_file_name(name: &str, no_spaces: bool) -> String {
+	let cleaned: String = name
+		.chars()
+		.map(|c| match c {
+			'<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+			c if c.is_control() => '_',
+			' ' if no_spaces => '_',
+			c => c,
+		})
+		.collect();
+	let trimmed = cleaned.trim().trim_end_matches('.').trim_end();
+	if trimmed.is_empty() {
+		"record".to_string()
+	} else {
+		trimmed.to_string()
+	}
+}
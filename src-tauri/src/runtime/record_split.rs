@@ -0,0 +1,416 @@
+// Recording split: native ffmpeg_muxer segmenting with a stop/restart fallback.
+
+use tauri::Manager;
+
+struct SplitSession {
+	template: String,
+	scene: Option<String>,
+	current: std::path::PathBuf,
+	// Final name for `current` when libobs picked the file name itself.
+	pending_name: Option<std::path::PathBuf>,
+	segments: Vec<std::path::PathBuf>,
+	native: bool,
+	split: Option<crate::settings::core::RecordingSplitSettings>,
+	segment_started: std::time::Instant,
+	generation: u64,
+}
+
+static SPLIT_SESSION: std::sync::OnceLock<std::sync::Mutex<Option<SplitSession>>> =
+	std::sync::OnceLock::new();
+static SESSION_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+const SPLIT_WAIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+fn split_session() -> &'static std::sync::Mutex<Option<SplitSession>> {
+	SPLIT_SESSION.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+fn reserve_segment_name(
+	template: &str,
+	scene: Option<&str>,
+	actual: &std::path::Path,
+) -> Option<std::path::PathBuf> {
+	let reserved = super::helpers::resolve_record_path(template, scene).ok()?;
+	Some(match actual.extension() {
+		Some(ext) => reserved.with_extension(ext),
+		None => reserved,
+	})
+}
+
+fn finalize_segment(
+	actual: std::path::PathBuf,
+	pending_name: Option<std::path::PathBuf>,
+) -> std::path::PathBuf {
+	let Some(target) = pending_name else {
+		return actual;
+	};
+	match std::fs::rename(&actual, &target) {
+		Ok(()) => target,
+		Err(_) => actual,
+	}
+}
+
+unsafe extern "C" fn file_changed_cb(
+	_data: *mut std::os::raw::c_void,
+	cd: *mut revo_lib::obs::calldata_t,
+) {
+	if cd.is_null() {
+		return;
+	}
+	let key = std::ffi::CString::new("next_file").unwrap();
+	let mut next_ptr: *const std::os::raw::c_char = std::ptr::null();
+	if !revo_lib::obs::calldata_get_string(cd, key.as_ptr(), &mut next_ptr) {
+		return;
+	}
+	let next_file = super::helpers::cstr_to_string(next_ptr);
+	if next_file.is_empty() {
+		return;
+	}
+
+	let Ok(mut guard) = split_session().lock() else {
+		return;
+	};
+	let Some(session) = guard.as_mut() else {
+		return;
+	};
+	let closed = std::mem::replace(&mut session.current, std::path::PathBuf::from(next_file));
+	let closed = finalize_segment(closed, session.pending_name.take());
+	crate::logging::debug::push_debug_log_entry(
+		"recording_segment_closed".to_string(),
+		Some(serde_json::json!({ "path": closed.to_string_lossy() })),
+	);
+	session.segments.push(closed);
+	session.pending_name =
+		reserve_segment_name(&session.template, session.scene.as_deref(), &session.current);
+	session.segment_started = std::time::Instant::now();
+}
+
+fn set_file_changed_signal(output: *mut revo_lib::obs::obs_output, connect: bool) {
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_signal_handler(output);
+		if handler.is_null() {
+			return;
+		}
+		let signal = std::ffi::CString::new("file_changed").unwrap();
+		if connect {
+			revo_lib::obs::signal_handler_connect(
+				handler,
+				signal.as_ptr(),
+				Some(file_changed_cb),
+				std::ptr::null_mut(),
+			);
+		} else {
+			revo_lib::obs::signal_handler_disconnect(
+				handler,
+				signal.as_ptr(),
+				Some(file_changed_cb),
+				std::ptr::null_mut(),
+			);
+		}
+	}
+}
+
+pub(crate) fn detach_split_signal(output: *mut revo_lib::obs::obs_output) {
+	if !output.is_null() {
+		set_file_changed_signal(output, false);
+	}
+}
+
+// Enables ffmpeg_muxer splitting before start. Split is always armed so the
+// on-demand command works; zero limits mean "manual only".
+pub(crate) fn prepare_output(
+	output: *mut revo_lib::obs::obs_output,
+	path: &std::path::Path,
+	split: Option<&crate::settings::core::RecordingSplitSettings>,
+) -> bool {
+	if output.is_null() {
+		return false;
+	}
+	let id = unsafe { super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_id(output)) };
+	if id != "ffmpeg_muxer" {
+		return false;
+	}
+	let directory = path
+		.parent()
+		.map(|p| p.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let extension = path
+		.extension()
+		.map(|e| e.to_string_lossy().to_string())
+		.unwrap_or_else(|| "mp4".to_string());
+	unsafe {
+		let settings = revo_lib::obs::obs_output_get_settings(output);
+		if settings.is_null() {
+			return false;
+		}
+		let split_key = std::ffi::CString::new("split_file").unwrap();
+		revo_lib::obs::obs_data_set_bool(settings, split_key.as_ptr(), true);
+
+		let time_key = std::ffi::CString::new("max_time_sec").unwrap();
+		revo_lib::obs::obs_data_set_int(
+			settings,
+			time_key.as_ptr(),
+			split.map(|s| s.max_time_sec).unwrap_or(0),
+		);
+
+		let size_key = std::ffi::CString::new("max_size_mb").unwrap();
+		revo_lib::obs::obs_data_set_int(
+			settings,
+			size_key.as_ptr(),
+			split.map(|s| s.max_size_mb).unwrap_or(0),
+		);
+
+		// Names libobs uses for follow-up segments; they are renamed through
+		// resolve_record_path once closed.
+		let dir_key = std::ffi::CString::new("directory").unwrap();
+		let dir_val = std::ffi::CString::new(directory).unwrap_or_default();
+		revo_lib::obs::obs_data_set_string(settings, dir_key.as_ptr(), dir_val.as_ptr());
+
+		let format_key = std::ffi::CString::new("format").unwrap();
+		let format_val = std::ffi::CString::new("segment_%CCYY-%MM-%DD_%hh-%mm-%ss").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, format_key.as_ptr(), format_val.as_ptr());
+
+		let ext_key = std::ffi::CString::new("extension").unwrap();
+		let ext_val = std::ffi::CString::new(extension).unwrap_or_default();
+		revo_lib::obs::obs_data_set_string(settings, ext_key.as_ptr(), ext_val.as_ptr());
+
+		let spaces_key = std::ffi::CString::new("allow_spaces").unwrap();
+		revo_lib::obs::obs_data_set_bool(settings, spaces_key.as_ptr(), false);
+
+		let overwrite_key = std::ffi::CString::new("allow_overwrite").unwrap();
+		revo_lib::obs::obs_data_set_bool(settings, overwrite_key.as_ptr(), false);
+
+		revo_lib::obs::obs_output_update(output, settings);
+		revo_lib::obs::obs_data_release(settings);
+	}
+	set_file_changed_signal(output, true);
+	true
+}
+
+pub(crate) fn begin_session(
+	template: &str,
+	scene: Option<&str>,
+	first_path: &std::path::Path,
+	native: bool,
+	split: Option<crate::settings::core::RecordingSplitSettings>,
+) {
+	let generation = SESSION_GENERATION.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+	let needs_watchdog = !native
+		&& split
+			.as_ref()
+			.is_some_and(|s| s.mode != crate::settings::core::RecordingSplitMode::Manual);
+	if let Ok(mut guard) = split_session().lock() {
+		*guard = Some(SplitSession {
+			template: template.to_string(),
+			scene: scene.map(str::to_string),
+			current: first_path.to_path_buf(),
+			pending_name: None,
+			segments: Vec::new(),
+			native,
+			split: split.clone(),
+			segment_started: std::time::Instant::now(),
+			generation,
+		});
+	}
+	if let (true, Some(split)) = (needs_watchdog, split) {
+		spawn_fallback_watchdog(generation, split);
+	}
+}
+
+pub(crate) fn finish_session() -> Vec<std::path::PathBuf> {
+	let session = split_session().lock().ok().and_then(|mut g| g.take());
+	let Some(mut session) = session else {
+		return Vec::new();
+	};
+	let last = finalize_segment(session.current, session.pending_name.take());
+	session.segments.push(last);
+	session.segments
+}
+
+pub(crate) fn session_segments() -> Vec<std::path::PathBuf> {
+	let Ok(guard) = split_session().lock() else {
+		return Vec::new();
+	};
+	let Some(session) = guard.as_ref() else {
+		return Vec::new();
+	};
+	let mut out = session.segments.clone();
+	out.push(session.current.clone());
+	out
+}
+
+// Fallback for outputs without native splitting: stop the output and start a
+// fresh one on the next name, keeping the recording timers running.
+fn restart_segment(runtime: &mut crate::ObsRuntime) -> Result<(), String> {
+	if runtime.record_paused_at.is_some() {
+		return Err("Cannot split a paused recording on this output".to_string());
+	}
+	let (template, split) = {
+		let guard = split_session()
+			.lock()
+			.map_err(|_| "split state poisoned".to_string())?;
+		let session = guard
+			.as_ref()
+			.ok_or_else(|| "No recording session".to_string())?;
+		(session.template.clone(), session.split.clone())
+	};
+
+	super::helpers::release_record_output(runtime);
+	match super::recording::start_recording_internal(runtime, &template, split.as_ref()) {
+		Ok((path, native)) => {
+			let mut watchdog = None;
+			if let Ok(mut guard) = split_session().lock() {
+				if let Some(session) = guard.as_mut() {
+					let closed = std::mem::replace(&mut session.current, path);
+					let closed = finalize_segment(closed, session.pending_name.take());
+					session.segments.push(closed);
+					// An output that lost native splitting needs the watchdog for
+					// the rest of the recording; a new generation retires any
+					// watchdog still running from an earlier switch.
+					if session.native && !native {
+						session.generation =
+							SESSION_GENERATION.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+						watchdog = session
+							.split
+							.clone()
+							.filter(|s| s.mode != crate::settings::core::RecordingSplitMode::Manual)
+							.map(|s| (session.generation, s));
+					}
+					session.native = native;
+					session.scene = runtime.current_scene.clone();
+					session.segment_started = std::time::Instant::now();
+				}
+			}
+			if let Some((generation, split)) = watchdog {
+				spawn_fallback_watchdog(generation, split);
+			}
+			Ok(())
+		}
+		Err(err) => {
+			// Same post-stop path as stop_recording, so closed segments are
+			// remuxed and the frontend sees the recording end.
+			let segments = super::helpers::stop_recording_internal(runtime);
+			super::remux::remux_after_recording(&segments);
+			let list: Vec<String> = segments
+				.iter()
+				.map(|p| p.to_string_lossy().to_string())
+				.collect();
+			let message = format!(
+				"Recording stopped while starting next segment: {err} (saved: {})",
+				list.join(", ")
+			);
+			super::output_state::mark_start_failed(super::output_state::RECORD_LABEL, &message);
+			Err(message)
+		}
+	}
+}
+
+fn spawn_fallback_watchdog(generation: u64, split: crate::settings::core::RecordingSplitSettings) {
+	std::thread::spawn(move || loop {
+		std::thread::sleep(std::time::Duration::from_secs(1));
+		let Some(app) = super::output_state::app_handle() else {
+			return;
+		};
+		let state = app.state::<crate::ObsState>();
+		let Ok(mut runtime) = state.runtime.lock() else {
+			return;
+		};
+		if runtime.output_record.is_null() {
+			return;
+		}
+		let segment_started = {
+			let Ok(guard) = split_session().lock() else {
+				return;
+			};
+			match guard.as_ref() {
+				Some(session) if session.generation == generation && !session.native => {
+					session.segment_started
+				}
+				_ => return,
+			}
+		};
+		let due = match split.mode {
+			crate::settings::core::RecordingSplitMode::Time => {
+				segment_started.elapsed().as_secs() as i64 >= split.max_time_sec
+			}
+			crate::settings::core::RecordingSplitMode::Size => {
+				let bytes = unsafe { revo_lib::obs::obs_output_get_total_bytes(runtime.output_record) };
+				bytes >= (split.max_size_mb as u64) * 1024 * 1024
+			}
+			crate::settings::core::RecordingSplitMode::Manual => false,
+		};
+		if due {
+			if let Err(err) = restart_segment(&mut runtime) {
+				crate::logging::debug::push_debug_log_entry(
+					"recording_split_failed".to_string(),
+					Some(serde_json::json!({ "error": err })),
+				);
+				return;
+			}
+		}
+	});
+}
+
+pub(crate) fn split_recording(state: tauri::State<crate::ObsState>) -> Result<Vec<String>, String> {
+	let before = {
+		let mut runtime = state
+			.runtime
+			.lock()
+			.map_err(|_| "state poisoned".to_string())?;
+		if runtime.output_record.is_null() {
+			return Err("Recording not active".to_string());
+		}
+		let native = split_session()
+			.lock()
+			.map_err(|_| "split state poisoned".to_string())?
+			.as_ref()
+			.map(|s| (s.native, s.segments.len()));
+		match native {
+			Some((true, closed)) => {
+				let requested = unsafe {
+					let handler = revo_lib::obs::obs_output_get_proc_handler(runtime.output_record);
+					let name = std::ffi::CString::new("split_file").unwrap();
+					let mut cd: revo_lib::obs::calldata_t = std::mem::zeroed();
+					let ok = !handler.is_null()
+						&& revo_lib::obs::proc_handler_call(handler, name.as_ptr(), &mut cd);
+					if !cd.fixed && !cd.stack.is_null() {
+						revo_lib::obs::bfree(cd.stack as *mut std::os::raw::c_void);
+					}
+					ok
+				};
+				if requested {
+					Some(closed)
+				} else {
+					restart_segment(&mut runtime)?;
+					None
+				}
+			}
+			Some((false, _)) => {
+				restart_segment(&mut runtime)?;
+				None
+			}
+			None => return Err("No recording session".to_string()),
+		}
+	};
+
+	// The muxer splits at the next keyframe; wait for file_changed to land.
+	if let Some(closed) = before {
+		let deadline = std::time::Instant::now() + SPLIT_WAIT_TIMEOUT;
+		while std::time::Instant::now() < deadline {
+			let done = split_session()
+				.lock()
+				.ok()
+				.and_then(|g| g.as_ref().map(|s| s.segments.len() > closed))
+				.unwrap_or(true);
+			if done {
+				break;
+			}
+			std::thread::sleep(std::time::Duration::from_millis(100));
+		}
+	}
+
+	Ok(session_segments()
+		.iter()
+		.map(|p| p.to_string_lossy().to_string())
+		.collect())
+}
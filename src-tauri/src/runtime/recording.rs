@@ -13,45 +13,76 @@ pub(crate) fn start_recording(
 		return Ok("Recording already active".to_string());
 	}
 
-	let resolved_path = super::helpers::resolve_record_path(&output_path)?;
+	let settings = crate::settings::core::settings_get()?;
+	// A URL target has no file to split.
+	let to_url = crate::settings::core::recording_custom_output_settings(&settings)?
+		.is_some_and(|c| c.target == crate::settings::core::RecordingCustomTarget::Url);
+	let split = if to_url {
+		None
+	} else {
+		crate::settings::core::recording_split_settings(&settings)?
+	};
+	let (resolved_path, native_split) =
+		start_recording_internal(&mut runtime, &output_path, split.as_ref())?;
+	runtime.record_started_at = Some(std::time::Instant::now());
+	super::record_split::begin_session(
+		&output_path,
+		runtime.current_scene.as_deref(),
+		&resolved_path,
+		native_split,
+		split,
+	);
+	super::recording_history::begin_session(&runtime, &resolved_path);
+	let disk_warning = if to_url {
+		None
+	} else {
+		resolved_path.parent().and_then(|dir| {
+			super::disk_watchdog::begin_watch(super::output_state::RECORD_LABEL, dir)
+		})
+	};
+	match disk_warning {
+		Some(warning) => Ok(format!(
+			"Recording started: {} (warning: {warning})",
+			resolved_path.to_string_lossy()
+		)),
+		None => Ok(format!("Recording started: {}", resolved_path.to_string_lossy())),
+	}
+}
+
+// Creates and starts the recording output (ffmpeg_muxer first, ffmpeg_output as
+// fallback). Returns the file path and whether the muxer can split natively.
+pub(crate) fn start_recording_internal(
+	runtime: &mut crate::ObsRuntime,
+	output_path: &str,
+	split: Option<&crate::settings::core::RecordingSplitSettings>,
+) -> Result<(std::path::PathBuf, bool), String> {
+	let audio_tracks = enabled_audio_tracks(runtime)?;
+	let settings = crate::settings::core::settings_get()?;
+	if let Some(custom) = crate::settings::core::recording_custom_output_settings(&settings)? {
+		return start_custom_recording_internal(runtime, output_path, &custom, &audio_tracks);
+	}
+	let plan = recording_plan(output_path, &audio_tracks)?;
+	let resolved_path =
+		super::helpers::resolve_record_path(&plan.path, runtime.current_scene.as_deref())?;
+	if let Some(dir) = resolved_path.parent() {
+		super::disk_watchdog::ensure_space_for_start(dir)?;
+	}
 	let output = super::encoders::create_muxer_output_with_path(
 		resolved_path.to_string_lossy().as_ref(),
 		true,
+		&plan.output,
 	);
 	if output.is_null() {
 		return Err("failed to create recording output".to_string());
 	}
 
-	let flags = unsafe { revo_lib::obs::obs_output_get_flags(output) };
-	unsafe {
-		// Ensure recording uses at least mix 1 (bit 0), otherwise some outputs may start with video-only.
-		revo_lib::obs::obs_output_set_mixers(output, 1);
-	}
-	if (flags & revo_lib::obs::OBS_OUTPUT_ENCODED) != 0 {
-		let video_encoder = super::encoders::create_video_encoder(runtime.video_encoder_preference);
-		let audio_encoder = super::encoders::create_audio_encoder();
-		if video_encoder.is_null() || audio_encoder.is_null() {
-			unsafe { revo_lib::obs::obs_output_release(output) };
-			return Err("failed to create encoders".to_string());
-		}
-		unsafe {
-			revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
-			revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
-			revo_lib::obs::obs_output_set_video_encoder(output, video_encoder);
-			revo_lib::obs::obs_output_set_audio_encoder(output, audio_encoder, 0);
-		}
-		runtime.record_video_encoder = video_encoder;
-		runtime.record_audio_encoder = audio_encoder;
-	} else {
-		unsafe {
-			revo_lib::obs::obs_output_set_media(
-				output,
-				revo_lib::obs::obs_get_video(),
-				revo_lib::obs::obs_get_audio(),
-			);
-		}
+	if let Err(err) = attach_recording_encoders(runtime, output, &audio_tracks, &plan) {
+		unsafe { revo_lib::obs::obs_output_release(output) };
+		return Err(err);
 	}
 
+	let native_split = super::record_split::prepare_output(output, &resolved_path, split);
+	super::output_state::attach_output_signals(super::output_state::RECORD_LABEL, output);
 	let started = revo_lib::recording::RecordingOutput::from_raw(output)
 		.and_then(|o| o.start())
 		.is_ok();
@@ -59,44 +90,24 @@ pub(crate) fn start_recording(
 		let last_error = unsafe {
 			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
 		};
+		super::record_split::detach_split_signal(output);
+		super::output_state::detach_output_signals(super::output_state::RECORD_LABEL);
 		unsafe { revo_lib::obs::obs_output_release(output) };
 		let fallback = super::encoders::create_muxer_output_with_path(
 			resolved_path.to_string_lossy().as_ref(),
 			false,
+			&plan.output,
 		);
 		if fallback.is_null() {
+			release_recording_encoders(runtime);
 			return Err(format!("recording start failed: {last_error}"));
 		}
-		let flags = unsafe { revo_lib::obs::obs_output_get_flags(fallback) };
-		unsafe {
-			// Ensure fallback recording output uses at least mix 1 as well.
-			revo_lib::obs::obs_output_set_mixers(fallback, 1);
-		}
-		if (flags & revo_lib::obs::OBS_OUTPUT_ENCODED) != 0 {
-			let video_encoder = super::encoders::create_video_encoder(runtime.video_encoder_preference);
-			let audio_encoder = super::encoders::create_audio_encoder();
-			if video_encoder.is_null() || audio_encoder.is_null() {
-				unsafe { revo_lib::obs::obs_output_release(fallback) };
-				return Err("failed to create encoders".to_string());
-			}
-			unsafe {
-				revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
-				revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
-				revo_lib::obs::obs_output_set_video_encoder(fallback, video_encoder);
-				revo_lib::obs::obs_output_set_audio_encoder(fallback, audio_encoder, 0);
-			}
-			runtime.record_video_encoder = video_encoder;
-			runtime.record_audio_encoder = audio_encoder;
-		} else {
-			unsafe {
-				revo_lib::obs::obs_output_set_media(
-					fallback,
-					revo_lib::obs::obs_get_video(),
-					revo_lib::obs::obs_get_audio(),
-				);
-			}
+		if let Err(err) = attach_recording_encoders(runtime, fallback, &audio_tracks, &plan) {
+			unsafe { revo_lib::obs::obs_output_release(fallback) };
+			return Err(err);
 		}
 
+		super::output_state::attach_output_signals(super::output_state::RECORD_LABEL, fallback);
 		let started_fallback = revo_lib::recording::RecordingOutput::from_raw(fallback)
 			.and_then(|o| o.start())
 			.is_ok();
@@ -104,17 +115,263 @@ pub(crate) fn start_recording(
 			let last_error = unsafe {
 				super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(fallback))
 			};
+			super::output_state::mark_start_failed(super::output_state::RECORD_LABEL, &last_error);
 			unsafe { revo_lib::obs::obs_output_release(fallback) };
+			release_recording_encoders(runtime);
 			return Err(format!("recording start failed: {last_error}"));
 		}
 		runtime.output_record = fallback;
 		runtime.last_record_path = Some(resolved_path.clone());
-		return Ok(format!("Recording started: {}", resolved_path.to_string_lossy()));
+		return Ok((resolved_path, false));
 	}
 
 	runtime.output_record = output;
 	runtime.last_record_path = Some(resolved_path.clone());
-	Ok(format!("Recording started: {}", resolved_path.to_string_lossy()))
+	Ok((resolved_path, native_split))
+}
+
+// Custom output mode: a raw ffmpeg_output encodes the selected mixes itself
+// and writes whatever FFmpeg format the profile names, to a file or a URL.
+fn start_custom_recording_internal(
+	runtime: &mut crate::ObsRuntime,
+	output_path: &str,
+	custom: &crate::settings::core::RecordingCustomOutputSettings,
+	tracks: &[crate::settings::core::AudioTrackSettings],
+) -> Result<(std::path::PathBuf, bool), String> {
+	let target = match custom.target {
+		crate::settings::core::RecordingCustomTarget::Url => {
+			std::path::PathBuf::from(custom.path.clone().unwrap_or_default())
+		}
+		crate::settings::core::RecordingCustomTarget::File => {
+			let requested = custom.path.as_deref().unwrap_or(output_path);
+			let path = super::helpers::resolve_record_path(
+				&super::helpers::with_record_extension(requested, &custom.extension),
+				runtime.current_scene.as_deref(),
+			)?;
+			if let Some(dir) = path.parent() {
+				super::disk_watchdog::ensure_space_for_start(dir)?;
+			}
+			path
+		}
+	};
+	release_recording_encoders(runtime);
+	let output =
+		super::encoders::create_custom_ffmpeg_output(target.to_string_lossy().as_ref(), custom);
+	if output.is_null() {
+		return Err("failed to create custom recording output".to_string());
+	}
+	unsafe {
+		revo_lib::obs::obs_output_set_mixers(output, tracks_mixer_mask(tracks).max(1));
+		revo_lib::obs::obs_output_set_media(
+			output,
+			revo_lib::obs::obs_get_video(),
+			revo_lib::obs::obs_get_audio(),
+		);
+	}
+
+	super::output_state::attach_output_signals(super::output_state::RECORD_LABEL, output);
+	let started = revo_lib::recording::RecordingOutput::from_raw(output)
+		.and_then(|o| o.start())
+		.is_ok();
+	if !started {
+		let last_error = unsafe {
+			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
+		};
+		super::output_state::mark_start_failed(super::output_state::RECORD_LABEL, &last_error);
+		unsafe { revo_lib::obs::obs_output_release(output) };
+		return Err(format!("custom recording output failed to start: {last_error}"));
+	}
+
+	runtime.output_record = output;
+	runtime.last_record_path = Some(target.clone());
+	Ok((target, false))
+}
+
+// Encoders and container details resolved from the profile for one recording.
+struct RecordingPlan {
+	/// Requested path, with the extension switched to the profile's container.
+	path: String,
+	video_encoder: Option<String>,
+	audio_encoder: String,
+	video_bitrate: i64,
+	output: super::encoders::RecordOutputOptions,
+}
+
+fn recording_plan(
+	output_path: &str,
+	tracks: &[crate::settings::core::AudioTrackSettings],
+) -> Result<RecordingPlan, String> {
+	let settings = crate::settings::core::settings_get()?;
+	let config = crate::settings::core::recording_output_settings(&settings)?;
+
+	let video_encoder = config
+		.video_encoder
+		.as_deref()
+		.map(super::encoders::resolve_encoder_id)
+		.transpose()?;
+	let audio_encoder =
+		super::encoders::resolve_encoder_id(config.audio_encoder.as_deref().unwrap_or("aac"))?;
+	// Every encoder create_video_encoder falls back to is H.264.
+	let video_codec = video_encoder
+		.as_deref()
+		.and_then(super::encoders::encoder_codec)
+		.unwrap_or_else(|| "h264".to_string());
+	let audio_codec =
+		super::encoders::encoder_codec(&audio_encoder).unwrap_or_else(|| "aac".to_string());
+	super::encoders::ensure_video_format_supported(&video_codec)?;
+
+	let path = match config.container {
+		Some(container) => super::helpers::with_record_extension(output_path, container.extension),
+		None => output_path.to_string(),
+	};
+	let container = config.container.or_else(|| {
+		std::path::Path::new(path.trim())
+			.extension()
+			.and_then(|e| e.to_str())
+			.and_then(crate::settings::core::recording_container)
+	});
+	if let Some(container) = container {
+		crate::settings::core::validate_recording_codecs(container, &video_codec, &audio_codec)?;
+	}
+
+	Ok(RecordingPlan {
+		path,
+		video_encoder,
+		audio_encoder,
+		video_bitrate: config.video_bitrate,
+		output: super::encoders::RecordOutputOptions {
+			format_name: container.map(|c| c.format_name).unwrap_or("mp4").to_string(),
+			muxer_settings: config.muxer_settings_string(),
+			video_codec,
+			audio_codec,
+			video_bitrate: config.video_bitrate,
+			audio_bitrate: tracks
+				.first()
+				.map(|t| t.bitrate)
+				.unwrap_or(crate::settings::core::AUDIO_TRACK_DEFAULT_BITRATE),
+		},
+	})
+}
+
+// Track 1 is always recorded; other tracks only when some source routes audio to them.
+fn enabled_audio_tracks(
+	runtime: &crate::ObsRuntime,
+) -> Result<Vec<crate::settings::core::AudioTrackSettings>, String> {
+	let settings = crate::settings::core::settings_get()?;
+	let tracks = crate::settings::core::audio_track_settings(&settings)?;
+
+	let mut mask: u32 = 1;
+	for scene in runtime.scenes.values() {
+		for item in scene.custom_items.values() {
+			if item.is_null() {
+				continue;
+			}
+			unsafe {
+				let source = revo_lib::obs::obs_sceneitem_get_source(*item);
+				if source.is_null()
+					|| (revo_lib::obs::obs_source_get_output_flags(source)
+						& revo_lib::obs::OBS_SOURCE_AUDIO)
+						== 0
+				{
+					continue;
+				}
+				mask |= revo_lib::obs::obs_source_get_audio_mixers(source);
+			}
+		}
+	}
+
+	Ok(tracks
+		.into_iter()
+		.filter(|t| mask & (1_u32 << (t.index - 1)) != 0)
+		.collect())
+}
+
+fn tracks_mixer_mask(tracks: &[crate::settings::core::AudioTrackSettings]) -> usize {
+	tracks
+		.iter()
+		.fold(0_usize, |mask, t| mask | (1_usize << (t.index - 1)))
+}
+
+fn release_recording_encoders(runtime: &mut crate::ObsRuntime) {
+	unsafe {
+		if !runtime.record_video_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(runtime.record_video_encoder);
+		}
+		for encoder in runtime.record_audio_encoders.drain(..) {
+			if !encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(encoder);
+			}
+		}
+	}
+	runtime.record_video_encoder = std::ptr::null_mut();
+}
+
+// Encoded outputs get one audio encoder per track at consecutive output indexes;
+// raw outputs (ffmpeg_output) encode the selected mixes themselves.
+fn attach_recording_encoders(
+	runtime: &mut crate::ObsRuntime,
+	output: *mut revo_lib::obs::obs_output,
+	tracks: &[crate::settings::core::AudioTrackSettings],
+	plan: &RecordingPlan,
+) -> Result<(), String> {
+	release_recording_encoders(runtime);
+	let flags = unsafe { revo_lib::obs::obs_output_get_flags(output) };
+	unsafe {
+		revo_lib::obs::obs_output_set_mixers(output, tracks_mixer_mask(tracks).max(1));
+	}
+	if (flags & revo_lib::obs::OBS_OUTPUT_ENCODED) == 0 {
+		unsafe {
+			revo_lib::obs::obs_output_set_media(
+				output,
+				revo_lib::obs::obs_get_video(),
+				revo_lib::obs::obs_get_audio(),
+			);
+		}
+		return Ok(());
+	}
+
+	let encoder_settings = crate::settings::core::StreamingEncoderSettings {
+		bitrate: plan.video_bitrate,
+		..Default::default()
+	};
+	let video_encoder = match plan.video_encoder.as_deref() {
+		Some(id) if super::encoders::is_x264_encoder_id(id) => {
+			super::encoders::create_x264_encoder(&encoder_settings)
+		}
+		Some(id) => super::encoders::try_create_encoder(id, "revo_record_video", &encoder_settings),
+		None => super::encoders::create_video_encoder(
+			runtime.video_encoder_preference,
+			&encoder_settings,
+		),
+	};
+	if video_encoder.is_null() {
+		return Err("failed to create encoders".to_string());
+	}
+	runtime.record_video_encoder = video_encoder;
+	unsafe {
+		revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
+		revo_lib::obs::obs_output_set_video_encoder(output, video_encoder);
+	}
+
+	let multi_track = (flags & revo_lib::obs::OBS_OUTPUT_MULTI_TRACK) != 0;
+	let tracks = if multi_track { tracks } else { &tracks[..tracks.len().min(1)] };
+	for (output_idx, track) in tracks.iter().enumerate() {
+		let audio_encoder = super::encoders::create_track_audio_encoder(track, &plan.audio_encoder);
+		if audio_encoder.is_null() {
+			release_recording_encoders(runtime);
+			return Err(format!("failed to create audio encoder for track {}", track.index));
+		}
+		runtime.record_audio_encoders.push(audio_encoder);
+		unsafe {
+			revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
+			revo_lib::obs::obs_output_set_audio_encoder(output, audio_encoder, output_idx);
+		}
+	}
+	if runtime.record_audio_encoders.is_empty() {
+		release_recording_encoders(runtime);
+		return Err("failed to create encoders".to_string());
+	}
+	Ok(())
 }
 
 pub(crate) fn stop_recording(state: tauri::State<crate::ObsState>) -> Result<String, String> {
@@ -126,10 +383,122 @@ pub(crate) fn stop_recording(state: tauri::State<crate::ObsState>) -> Result<Str
 		return Ok("Recording not active".to_string());
 	}
 	let last_path = runtime.last_record_path.clone();
-	super::helpers::stop_recording_internal(&mut runtime);
-	if let Some(path) = last_path {
+	let mut segments = super::helpers::stop_recording_internal(&mut runtime);
+	if segments.is_empty() {
+		segments.extend(last_path.clone());
+	}
+	drop(runtime);
+	super::remux::remux_after_recording(&segments);
+	if segments.len() > 1 {
+		let list: Vec<String> = segments
+			.iter()
+			.map(|p| p.to_string_lossy().to_string())
+			.collect();
+		Ok(format!("Recording stopped ({} segments): {}", segments.len(), list.join(", ")))
+	} else if let Some(path) = segments.last() {
 		Ok(format!("Recording stopped: {}", path.to_string_lossy()))
 	} else {
 		Ok("Recording stopped".to_string())
 	}
 }
+
+fn recording_can_pause(output: *mut revo_lib::obs::obs_output) -> bool {
+	!output.is_null() && unsafe { revo_lib::obs::obs_output_can_pause(output) }
+}
+
+pub(crate) fn pause_recording(state: tauri::State<crate::ObsState>) -> Result<String, String> {
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if runtime.output_record.is_null() {
+		return Err("Recording not active".to_string());
+	}
+	if runtime.record_paused_at.is_some() {
+		return Ok("Recording already paused".to_string());
+	}
+	if !recording_can_pause(runtime.output_record) {
+		return Err("The active recording output does not support pausing".to_string());
+	}
+	if !unsafe { revo_lib::obs::obs_output_pause(runtime.output_record, true) } {
+		let last_error = unsafe {
+			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(
+				runtime.output_record,
+			))
+		};
+		return Err(format!("failed to pause recording: {last_error}"));
+	}
+	runtime.record_paused_at = Some(std::time::Instant::now());
+	Ok("Recording paused".to_string())
+}
+
+pub(crate) fn resume_recording(state: tauri::State<crate::ObsState>) -> Result<String, String> {
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if runtime.output_record.is_null() {
+		return Err("Recording not active".to_string());
+	}
+	let Some(paused_at) = runtime.record_paused_at else {
+		return Ok("Recording not paused".to_string());
+	};
+	if !unsafe { revo_lib::obs::obs_output_pause(runtime.output_record, false) } {
+		let last_error = unsafe {
+			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(
+				runtime.output_record,
+			))
+		};
+		return Err(format!("failed to resume recording: {last_error}"));
+	}
+	runtime.record_paused_total += paused_at.elapsed();
+	runtime.record_paused_at = None;
+	Ok("Recording resumed".to_string())
+}
+
+pub(crate) fn get_recording_state(
+	state: tauri::State<crate::ObsState>,
+) -> Result<crate::RecordingState, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	let output = runtime.output_record;
+	let active = !output.is_null() && unsafe { revo_lib::obs::obs_output_active(output) };
+	let paused = !output.is_null() && unsafe { revo_lib::obs::obs_output_paused(output) };
+
+	let (elapsed, paused_total) = recording_elapsed(&runtime);
+
+	Ok(crate::RecordingState {
+		active,
+		paused,
+		can_pause: recording_can_pause(output),
+		path: runtime
+			.last_record_path
+			.as_ref()
+			.map(|p| p.to_string_lossy().to_string()),
+		elapsed_ms: elapsed.as_millis() as u64,
+		paused_ms: paused_total.as_millis() as u64,
+		segments: super::record_split::session_segments()
+			.iter()
+			.map(|p| p.to_string_lossy().to_string())
+			.collect(),
+		chapters: super::record_chapters::session_chapters(),
+	})
+}
+
+/// Recording time with pauses excluded, and the total time spent paused.
+pub(crate) fn recording_elapsed(
+	runtime: &crate::ObsRuntime,
+) -> (std::time::Duration, std::time::Duration) {
+	let paused_total = runtime.record_paused_total
+		+ runtime
+			.record_paused_at
+			.map(|at| at.elapsed())
+			.unwrap_or_default();
+	let elapsed = runtime
+		.record_started_at
+		.map(|at| at.elapsed().saturating_sub(paused_total))
+		.unwrap_or_default();
+	(elapsed, paused_total)
+}
@@ -0,0 +1,302 @@
+// Persistent index of finished recordings (data/recording_history.json).
+
+const HISTORY_FILE: &str = "recording_history.json";
+
+struct HistorySession {
+	started_ms: u64,
+	container: String,
+	video_encoder: Option<String>,
+	audio_encoders: Vec<String>,
+	profile: String,
+	scenes: Vec<String>,
+}
+
+static HISTORY_SESSION: std::sync::OnceLock<std::sync::Mutex<Option<HistorySession>>> =
+	std::sync::OnceLock::new();
+// Serializes read-modify-write cycles on the history file.
+static HISTORY_FILE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn history_session() -> &'static std::sync::Mutex<Option<HistorySession>> {
+	HISTORY_SESSION.get_or_init(|| std::sync::Mutex::new(None))
+}
+
+fn history_file() -> Result<std::path::PathBuf, String> {
+	Ok(crate::settings::core::runtime_data_dir()?.join(HISTORY_FILE))
+}
+
+fn load_history() -> Result<Vec<crate::RecordingHistoryEntry>, String> {
+	let file = history_file()?;
+	if !file.is_file() {
+		return Ok(Vec::new());
+	}
+	let raw = std::fs::read_to_string(&file)
+		.map_err(|e| format!("failed to read recording history: {e}"))?;
+	if raw.trim().is_empty() {
+		return Ok(Vec::new());
+	}
+	serde_json::from_str(&raw).map_err(|e| format!("invalid recording history: {e}"))
+}
+
+fn save_history(entries: &[crate::RecordingHistoryEntry]) -> Result<(), String> {
+	let file = history_file()?;
+	if let Some(parent) = file.parent() {
+		std::fs::create_dir_all(parent).map_err(|e| format!("failed to create data dir: {e}"))?;
+	}
+	let raw = serde_json::to_string_pretty(entries)
+		.map_err(|e| format!("failed to serialize recording history: {e}"))?;
+	std::fs::write(&file, raw).map_err(|e| format!("failed to write recording history: {e}"))
+}
+
+fn encoder_id(encoder: *mut revo_lib::obs::obs_encoder) -> Option<String> {
+	if encoder.is_null() {
+		return None;
+	}
+	let id = unsafe { super::helpers::cstr_to_string(revo_lib::obs::obs_encoder_get_id(encoder)) };
+	Some(id).filter(|id| !id.is_empty())
+}
+
+fn output_setting(output: *mut revo_lib::obs::obs_output, key: &str) -> Option<String> {
+	unsafe {
+		let settings = revo_lib::obs::obs_output_get_settings(output);
+		if settings.is_null() {
+			return None;
+		}
+		let key_c = std::ffi::CString::new(key).unwrap();
+		let value = super::helpers::cstr_to_string(revo_lib::obs::obs_data_get_string(
+			settings,
+			key_c.as_ptr(),
+		));
+		revo_lib::obs::obs_data_release(settings);
+		Some(value).filter(|v| !v.is_empty())
+	}
+}
+
+/// Captures what is only known while the output exists (encoders, container).
+pub(crate) fn begin_session(runtime: &crate::ObsRuntime, path: &std::path::Path) {
+	let output = runtime.output_record;
+	if output.is_null() {
+		return;
+	}
+	// Raw outputs (custom FFmpeg mode) have no libobs encoders; their FFmpeg
+	// encoder and format names live in the output settings instead.
+	let raw_output = runtime.record_video_encoder.is_null();
+	let (video_encoder, audio_encoders) = if raw_output {
+		(
+			output_setting(output, "video_encoder"),
+			output_setting(output, "audio_encoder").into_iter().collect(),
+		)
+	} else {
+		(
+			encoder_id(runtime.record_video_encoder),
+			runtime
+				.record_audio_encoders
+				.iter()
+				.filter_map(|e| encoder_id(*e))
+				.collect(),
+		)
+	};
+	let container = raw_output
+		.then(|| output_setting(output, "format_name"))
+		.flatten()
+		.or_else(|| path.extension().map(|e| e.to_string_lossy().to_lowercase()))
+		.unwrap_or_default();
+	let profile = crate::settings::core::settings_get()
+		.ok()
+		.and_then(|s| s.active_profile)
+		.unwrap_or_else(|| "default".to_string());
+
+	if let Ok(mut guard) = history_session().lock() {
+		*guard = Some(HistorySession {
+			started_ms: super::output_state::now_ms(),
+			container,
+			video_encoder,
+			audio_encoders,
+			profile,
+			scenes: runtime.current_scene.clone().into_iter().collect(),
+		});
+	}
+}
+
+pub(crate) fn note_scene(scene: &str) {
+	if let Ok(mut guard) = history_session().lock() {
+		if let Some(session) = guard.as_mut() {
+			if !session.scenes.iter().any(|s| s == scene) {
+				session.scenes.push(scene.to_string());
+			}
+		}
+	}
+}
+
+/// Appends the finished recording to the history file.
+pub(crate) fn finish_session(segments: &[std::path::PathBuf], duration: std::time::Duration) {
+	let session = history_session().lock().ok().and_then(|mut g| g.take());
+	let (Some(session), Some(first)) = (session, segments.first()) else {
+		return;
+	};
+	let size_bytes = segments
+		.iter()
+		.filter_map(|p| std::fs::metadata(p).ok())
+		.map(|m| m.len())
+		.sum();
+	let entry = crate::RecordingHistoryEntry {
+		id: uuid::Uuid::new_v4().to_string(),
+		path: first.to_string_lossy().to_string(),
+		segments: segments.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+		started_ms: session.started_ms,
+		ended_ms: super::output_state::now_ms(),
+		duration_ms: duration.as_millis() as u64,
+		size_bytes,
+		container: session.container,
+		video_encoder: session.video_encoder,
+		audio_encoders: session.audio_encoders,
+		profile: session.profile,
+		scenes: session.scenes,
+		missing: false,
+	};
+	let result = HISTORY_FILE_LOCK
+		.lock()
+		.map_err(|_| "history state poisoned".to_string())
+		.and_then(|_guard| {
+			let mut entries = load_history()?;
+			entries.push(entry);
+			save_history(&entries)
+		});
+	if let Err(err) = result {
+		crate::logging::debug::push_debug_log_entry(
+			"recording_history_failed".to_string(),
+			Some(serde_json::json!({ "error": err })),
+		);
+	}
+}
+
+/// Points the entry that recorded `source` at its remuxed copy, so entries
+/// stay valid once the original is deleted.
+pub(crate) fn replace_segment(source: &str, target: &str) {
+	let result = HISTORY_FILE_LOCK
+		.lock()
+		.map_err(|_| "history state poisoned".to_string())
+		.and_then(|_guard| {
+			let mut entries = load_history()?;
+			let Some(entry) = entries.iter_mut().find(|e| e.segments.iter().any(|s| s == source))
+			else {
+				return Ok(());
+			};
+			for segment in entry.segments.iter_mut().filter(|s| s.as_str() == source) {
+				*segment = target.to_string();
+			}
+			if entry.path == source {
+				entry.path = target.to_string();
+			}
+			// Mixed containers only last until the remaining segments finish.
+			let containers: std::collections::BTreeSet<String> = entry
+				.segments
+				.iter()
+				.filter_map(|s| std::path::Path::new(s).extension())
+				.map(|e| e.to_string_lossy().to_lowercase())
+				.collect();
+			if containers.len() == 1 {
+				entry.container = containers.into_iter().next().unwrap_or_default();
+			}
+			entry.size_bytes = entry
+				.segments
+				.iter()
+				.filter_map(|p| std::fs::metadata(p).ok())
+				.map(|m| m.len())
+				.sum();
+			save_history(&entries)
+		});
+	if let Err(err) = result {
+		crate::logging::debug::push_debug_log_entry(
+			"recording_history_failed".to_string(),
+			Some(serde_json::json!({ "error": err })),
+		);
+	}
+}
+
+fn entry_matches(entry: &crate::RecordingHistoryEntry, query: &str) -> bool {
+	let query = query.to_lowercase();
+	std::iter::once(&entry.path)
+		.chain(std::iter::once(&entry.profile))
+		.chain(std::iter::once(&entry.container))
+		.chain(entry.video_encoder.iter())
+		.chain(entry.audio_encoders.iter())
+		.chain(entry.scenes.iter())
+		.any(|field| field.to_lowercase().contains(&query))
+}
+
+/// Newest first, optionally filtered by a case-insensitive substring over
+/// path, profile, container, encoders and scenes.
+pub(crate) fn list_recording_history(
+	query: Option<String>,
+) -> Result<Vec<crate::RecordingHistoryEntry>, String> {
+	let _guard = HISTORY_FILE_LOCK
+		.lock()
+		.map_err(|_| "history state poisoned".to_string())?;
+	let query = query.map(|q| q.trim().to_string()).filter(|q| !q.is_empty());
+	let mut entries: Vec<crate::RecordingHistoryEntry> = load_history()?
+		.into_iter()
+		.filter(|e| match query.as_deref() {
+			Some(q) => entry_matches(e, q),
+			None => true,
+		})
+		.map(|mut e| {
+			e.missing = e.segments.iter().any(|p| !std::path::Path::new(p).is_file());
+			e
+		})
+		.collect();
+	entries.sort_by(|a, b| b.started_ms.cmp(&a.started_ms));
+	Ok(entries)
+}
+
+fn find_entry(id: &str) -> Result<crate::RecordingHistoryEntry, String> {
+	let _guard = HISTORY_FILE_LOCK
+		.lock()
+		.map_err(|_| "history state poisoned".to_string())?;
+	load_history()?
+		.into_iter()
+		.find(|e| e.id == id)
+		.ok_or_else(|| format!("recording not found in history: {id}"))
+}
+
+pub(crate) fn reveal_recording(id: &str) -> Result<String, String> {
+	let entry = find_entry(id)?;
+	let path = std::path::PathBuf::from(&entry.path);
+	if !path.is_file() {
+		return Err(format!("recording file is missing: {}", entry.path));
+	}
+	tauri_plugin_opener::reveal_item_in_dir(&path)
+		.map_err(|e| format!("failed to reveal recording: {e}"))?;
+	Ok(format!("Revealed {}", entry.path))
+}
+
+/// Drops the entry; with `delete_files` its files are removed from disk too.
+pub(crate) fn delete_recording(id: &str, delete_files: bool) -> Result<String, String> {
+	let _guard = HISTORY_FILE_LOCK
+		.lock()
+		.map_err(|_| "history state poisoned".to_string())?;
+	let mut entries = load_history()?;
+	let idx = entries
+		.iter()
+		.position(|e| e.id == id)
+		.ok_or_else(|| format!("recording not found in history: {id}"))?;
+
+	let mut removed = 0;
+	if delete_files {
+		for segment in &entries[idx].segments {
+			let path = std::path::Path::new(segment);
+			if !path.is_file() {
+				continue;
+			}
+			std::fs::remove_file(path)
+				.map_err(|e| format!("failed to delete {segment}: {e}"))?;
+			removed += 1;
+		}
+	}
+	let entry = entries.remove(idx);
+	save_history(&entries)?;
+	if delete_files {
+		Ok(format!("Deleted {} ({removed} files removed)", entry.path))
+	} else {
+		Ok(format!("Removed {} from history", entry.path))
+	}
+}
@@ -0,0 +1,362 @@
+// Background remux of finished recordings to MP4 (stream copy, no re-encode).
+
+use std::io::BufRead;
+use std::io::Read;
+
+// Allowed drift between source and result when verifying a remux.
+const REMUX_VERIFY_TOLERANCE_SEC: f64 = 1.0;
+
+static REMUX_JOBS: std::sync::OnceLock<std::sync::Mutex<Vec<crate::RemuxJob>>> =
+	std::sync::OnceLock::new();
+static REMUX_WORKER_RUNNING: std::sync::atomic::AtomicBool =
+	std::sync::atomic::AtomicBool::new(false);
+static REMUX_TOOLS: std::sync::OnceLock<crate::RemuxToolsStatus> = std::sync::OnceLock::new();
+
+fn remux_jobs() -> &'static std::sync::Mutex<Vec<crate::RemuxJob>> {
+	REMUX_JOBS.get_or_init(|| std::sync::Mutex::new(Vec::new()))
+}
+
+pub(crate) fn ffmpeg_bin() -> String {
+	std::env::var("REVO_FFMPEG_BIN").unwrap_or_else(|_| "ffmpeg".to_string())
+}
+
+fn ffprobe_bin() -> String {
+	std::env::var("REVO_FFPROBE_BIN").unwrap_or_else(|_| "ffprobe".to_string())
+}
+
+fn tool_runs(bin: &str) -> bool {
+	std::process::Command::new(bin)
+		.arg("-version")
+		.stdin(std::process::Stdio::null())
+		.stdout(std::process::Stdio::null())
+		.stderr(std::process::Stdio::null())
+		.status()
+		.is_ok_and(|s| s.success())
+}
+
+/// Probed once (at startup) and cached; missing tools are logged and shown in
+/// the recording settings.
+pub(crate) fn remux_tools_status() -> crate::RemuxToolsStatus {
+	REMUX_TOOLS
+		.get_or_init(|| {
+			let status = crate::RemuxToolsStatus {
+				ffmpeg_available: tool_runs(&ffmpeg_bin()),
+				ffmpeg_bin: ffmpeg_bin(),
+				ffprobe_available: tool_runs(&ffprobe_bin()),
+				ffprobe_bin: ffprobe_bin(),
+			};
+			if !status.ffmpeg_available || !status.ffprobe_available {
+				crate::logging::debug::push_debug_log_entry(
+					"remux_tools_missing".to_string(),
+					serde_json::to_value(&status).ok(),
+				);
+			}
+			status
+		})
+		.clone()
+}
+
+fn update_job<F>(id: &str, apply: F)
+where
+	F: FnOnce(&mut crate::RemuxJob),
+{
+	if let Ok(mut jobs) = remux_jobs().lock() {
+		if let Some(job) = jobs.iter_mut().find(|j| j.id == id) {
+			apply(job);
+		}
+	}
+}
+
+pub(crate) fn probe_duration_sec(path: &std::path::Path) -> Option<f64> {
+	let output = std::process::Command::new(ffprobe_bin())
+		.args([
+			"-v",
+			"error",
+			"-show_entries",
+			"format=duration",
+			"-of",
+			"default=noprint_wrappers=1:nokey=1",
+		])
+		.arg(path)
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	String::from_utf8_lossy(&output.stdout)
+		.trim()
+		.parse::<f64>()
+		.ok()
+		.filter(|d| d.is_finite() && *d > 0.0)
+}
+
+fn remux_target_path(source: &std::path::Path) -> std::path::PathBuf {
+	let parent = source
+		.parent()
+		.map(std::path::PathBuf::from)
+		.unwrap_or_default();
+	let stem = source
+		.file_stem()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_else(|| "record".to_string());
+	let mut target = parent.join(format!("{stem}.mp4"));
+	let mut idx = 1;
+	while target.exists() || target == source {
+		target = parent.join(format!("{stem}_remux{idx}.mp4"));
+		idx += 1;
+	}
+	target
+}
+
+pub(crate) fn enqueue_remux(source: &str, delete_source: bool) -> Result<crate::RemuxJob, String> {
+	let source_path = std::path::PathBuf::from(source.trim());
+	if !source_path.is_file() {
+		return Err(format!("file not found: {}", source_path.to_string_lossy()));
+	}
+	let tools = remux_tools_status();
+	if !tools.ffmpeg_available {
+		return Err(format!(
+			"remuxing needs ffmpeg, but '{}' could not be run (install ffmpeg or set REVO_FFMPEG_BIN)",
+			tools.ffmpeg_bin
+		));
+	}
+	let source_str = source_path.to_string_lossy().to_string();
+	let mut jobs = remux_jobs()
+		.lock()
+		.map_err(|_| "remux state poisoned".to_string())?;
+	if let Some(existing) = jobs.iter().find(|j| {
+		j.source == source_str
+			&& matches!(j.status, crate::RemuxStatus::Queued | crate::RemuxStatus::Running)
+	}) {
+		return Ok(existing.clone());
+	}
+
+	let job = crate::RemuxJob {
+		id: uuid::Uuid::new_v4().to_string(),
+		source: source_str,
+		target: remux_target_path(&source_path).to_string_lossy().to_string(),
+		status: crate::RemuxStatus::Queued,
+		progress: 0.0,
+		error: None,
+		delete_source,
+		source_deleted: false,
+		source_kept_reason: None,
+		created_ms: super::output_state::now_ms(),
+		finished_ms: None,
+	};
+	jobs.push(job.clone());
+	drop(jobs);
+	ensure_worker();
+	Ok(job)
+}
+
+pub(crate) fn list_remux_jobs() -> Result<Vec<crate::RemuxJob>, String> {
+	Ok(remux_jobs()
+		.lock()
+		.map_err(|_| "remux state poisoned".to_string())?
+		.clone())
+}
+
+pub(crate) fn clear_finished_remux_jobs() -> Result<usize, String> {
+	let mut jobs = remux_jobs()
+		.lock()
+		.map_err(|_| "remux state poisoned".to_string())?;
+	let before = jobs.len();
+	jobs.retain(|j| matches!(j.status, crate::RemuxStatus::Queued | crate::RemuxStatus::Running));
+	Ok(before - jobs.len())
+}
+
+/// Queues every non-MP4 file from a finished recording when the profile asks for it.
+pub(crate) fn remux_after_recording(segments: &[std::path::PathBuf]) {
+	let Ok(settings) = crate::settings::core::settings_get() else {
+		return;
+	};
+	let config = crate::settings::core::recording_remux_settings(&settings);
+	if !config.auto_remux {
+		return;
+	}
+	for segment in segments {
+		let is_mp4 = segment
+			.extension()
+			.is_some_and(|e| e.eq_ignore_ascii_case("mp4"));
+		// Custom outputs may target a URL, which leaves nothing on disk.
+		if is_mp4 || !segment.is_file() {
+			continue;
+		}
+		if let Err(err) = enqueue_remux(&segment.to_string_lossy(), config.delete_source) {
+			crate::logging::debug::push_debug_log_entry(
+				"remux_enqueue_failed".to_string(),
+				Some(serde_json::json!({ "source": segment.to_string_lossy(), "error": err })),
+			);
+		}
+	}
+}
+
+// Jobs run one at a time; remuxing is disk-bound and parallel jobs only thrash.
+fn ensure_worker() {
+	if REMUX_WORKER_RUNNING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+		return;
+	}
+	std::thread::spawn(|| loop {
+		let next = remux_jobs().lock().ok().and_then(|mut jobs| {
+			let job = jobs
+				.iter_mut()
+				.find(|j| j.status == crate::RemuxStatus::Queued)?;
+			job.status = crate::RemuxStatus::Running;
+			Some(job.clone())
+		});
+		let Some(job) = next else {
+			REMUX_WORKER_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
+			// A job may have been queued between the lookup and the flag reset.
+			let pending = remux_jobs()
+				.lock()
+				.map(|jobs| jobs.iter().any(|j| j.status == crate::RemuxStatus::Queued))
+				.unwrap_or(false);
+			if pending && !REMUX_WORKER_RUNNING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+				continue;
+			}
+			return;
+		};
+
+		let result = run_remux(&job);
+		let finished_ms = super::output_state::now_ms();
+		match result {
+			Ok(source_deleted) => {
+				super::recording_history::replace_segment(&job.source, &job.target);
+				update_job(&job.id, |j| {
+					j.status = crate::RemuxStatus::Done;
+					j.progress = 1.0;
+					j.source_deleted = source_deleted;
+					j.finished_ms = Some(finished_ms);
+				})
+			}
+			Err(err) => update_job(&job.id, |j| {
+				j.status = crate::RemuxStatus::Failed;
+				j.error = Some(err);
+				j.finished_ms = Some(finished_ms);
+			}),
+		}
+		crate::logging::debug::push_debug_log_entry(
+			"remux_job_finished".to_string(),
+			remux_jobs()
+				.lock()
+				.ok()
+				.and_then(|jobs| jobs.iter().find(|j| j.id == job.id).cloned())
+				.and_then(|j| serde_json::to_value(j).ok()),
+		);
+	});
+}
+
+fn parse_progress_us(line: &str) -> Option<f64> {
+	let (key, value) = line.split_once('=')?;
+	match key.trim() {
+		// out_time_ms is microseconds as well (long-standing ffmpeg quirk).
+		"out_time_us" | "out_time_ms" => value.trim().parse::<f64>().ok(),
+		_ => None,
+	}
+}
+
+// Returns whether the source was deleted.
+fn run_remux(job: &crate::RemuxJob) -> Result<bool, String> {
+	let source = std::path::PathBuf::from(&job.source);
+	let target = std::path::PathBuf::from(&job.target);
+	let temp = target.with_extension("remux-tmp.mp4");
+	let duration = probe_duration_sec(&source);
+
+	let mut child = std::process::Command::new(ffmpeg_bin())
+		.args(["-hide_banner", "-loglevel", "error", "-nostats", "-y", "-i"])
+		.arg(&source)
+		.args([
+			"-map",
+			"0",
+			"-c",
+			"copy",
+			"-movflags",
+			"+faststart",
+			"-f",
+			"mp4",
+			"-progress",
+			"pipe:1",
+		])
+		.arg(&temp)
+		.stdin(std::process::Stdio::null())
+		.stdout(std::process::Stdio::piped())
+		.stderr(std::process::Stdio::piped())
+		.spawn()
+		.map_err(|e| format!("failed to run ffmpeg: {e}"))?;
+
+	if let Some(stdout) = child.stdout.take() {
+		for line in std::io::BufReader::new(stdout).lines().map_while(Result::ok) {
+			if line.trim() == "progress=end" {
+				update_job(&job.id, |j| j.progress = 1.0);
+				continue;
+			}
+			if let (Some(us), Some(total)) = (parse_progress_us(&line), duration) {
+				let progress = (us / 1_000_000.0 / total).clamp(0.0, 0.99);
+				update_job(&job.id, |j| j.progress = progress);
+			}
+		}
+	}
+	let mut stderr = String::new();
+	if let Some(mut pipe) = child.stderr.take() {
+		let _ = pipe.read_to_string(&mut stderr);
+	}
+	let status = child
+		.wait()
+		.map_err(|e| format!("failed to wait for ffmpeg: {e}"))?;
+	if !status.success() {
+		let _ = std::fs::remove_file(&temp);
+		let detail = stderr.trim();
+		return Err(if detail.is_empty() {
+			format!("ffmpeg exited with {status}")
+		} else {
+			format!("ffmpeg failed: {detail}")
+		});
+	}
+
+	let verified = match verify_remux(&temp, duration) {
+		Ok(verified) => verified,
+		Err(err) => {
+			let _ = std::fs::remove_file(&temp);
+			return Err(err);
+		}
+	};
+	std::fs::rename(&temp, &target).map_err(|e| format!("failed to finalize remux: {e}"))?;
+
+	if !job.delete_source {
+		return Ok(false);
+	}
+	// Deleting on a size check alone could throw away the only good copy.
+	if !verified {
+		update_job(&job.id, |j| {
+			j.source_kept_reason =
+				Some("durations could not be compared (ffprobe unavailable or failed)".to_string())
+		});
+		return Ok(false);
+	}
+	std::fs::remove_file(&source)
+		.map(|_| true)
+		.map_err(|e| format!("remux done but source could not be deleted: {e}"))
+}
+
+// Ok(true) only when source and result durations were both probed and match;
+// Ok(false) means the result exists but could not be compared.
+fn verify_remux(result: &std::path::Path, source_duration: Option<f64>) -> Result<bool, String> {
+	let size = std::fs::metadata(result)
+		.map(|m| m.len())
+		.map_err(|e| format!("remux output missing: {e}"))?;
+	if size == 0 {
+		return Err("remux output is empty".to_string());
+	}
+	let Some(expected) = source_duration else {
+		return Ok(false);
+	};
+	let actual = probe_duration_sec(result)
+		.ok_or_else(|| "remux output could not be probed".to_string())?;
+	if (actual - expected).abs() > REMUX_VERIFY_TOLERANCE_SEC.max(expected * 0.01) {
+		return Err(format!(
+			"remux duration mismatch: source {expected:.2}s, result {actual:.2}s"
+		));
+	}
+	Ok(true)
+}
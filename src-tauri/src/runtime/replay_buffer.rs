@@ -0,0 +1,376 @@
+// Replay buffer output: keeps the last N seconds in memory and writes them out on demand.
+
+const DEFAULT_REPLAY_MAX_SECONDS: i64 = 20;
+const DEFAULT_REPLAY_MAX_MEGABYTES: i64 = 512;
+const REPLAY_SAVE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+// Bumped by the "saved" signal; save_replay_buffer waits for it to move.
+static REPLAY_SAVES: std::sync::OnceLock<(std::sync::Mutex<u64>, std::sync::Condvar)> =
+	std::sync::OnceLock::new();
+
+fn replay_saves() -> &'static (std::sync::Mutex<u64>, std::sync::Condvar) {
+	REPLAY_SAVES.get_or_init(|| (std::sync::Mutex::new(0), std::sync::Condvar::new()))
+}
+
+unsafe extern "C" fn replay_saved_cb(
+	_data: *mut std::os::raw::c_void,
+	_cd: *mut revo_lib::obs::calldata_t,
+) {
+	let (lock, cvar) = replay_saves();
+	if let Ok(mut count) = lock.lock() {
+		*count = count.wrapping_add(1);
+		cvar.notify_all();
+	}
+}
+
+fn set_saved_signal(output: *mut revo_lib::obs::obs_output, connect: bool) {
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_signal_handler(output);
+		if handler.is_null() {
+			return;
+		}
+		let signal = std::ffi::CString::new("saved").unwrap();
+		if connect {
+			revo_lib::obs::signal_handler_connect(
+				handler,
+				signal.as_ptr(),
+				Some(replay_saved_cb),
+				std::ptr::null_mut(),
+			);
+		} else {
+			revo_lib::obs::signal_handler_disconnect(
+				handler,
+				signal.as_ptr(),
+				Some(replay_saved_cb),
+				std::ptr::null_mut(),
+			);
+		}
+	}
+}
+
+fn call_output_proc(output: *mut revo_lib::obs::obs_output, name: &str) -> Option<String> {
+	unsafe {
+		let handler = revo_lib::obs::obs_output_get_proc_handler(output);
+		if handler.is_null() {
+			return None;
+		}
+		let name_c = std::ffi::CString::new(name).unwrap();
+		let mut cd: revo_lib::obs::calldata_t = std::mem::zeroed();
+		if !revo_lib::obs::proc_handler_call(handler, name_c.as_ptr(), &mut cd) {
+			return None;
+		}
+		let key = std::ffi::CString::new("path").unwrap();
+		let mut path_ptr: *const std::os::raw::c_char = std::ptr::null();
+		let found = revo_lib::obs::calldata_get_string(&cd, key.as_ptr(), &mut path_ptr);
+		let path = if found {
+			super::helpers::cstr_to_string(path_ptr)
+		} else {
+			String::new()
+		};
+		if !cd.fixed && !cd.stack.is_null() {
+			revo_lib::obs::bfree(cd.stack as *mut std::os::raw::c_void);
+		}
+		Some(path)
+	}
+}
+
+fn replay_output_settings(
+	template: &std::path::Path,
+	max_seconds: i64,
+	max_megabytes: i64,
+) -> *mut revo_lib::obs::obs_data {
+	let directory = template
+		.parent()
+		.map(|p| p.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let extension = template
+		.extension()
+		.map(|e| e.to_string_lossy().to_string())
+		.unwrap_or_else(|| "mp4".to_string());
+	unsafe {
+		let settings = revo_lib::obs::obs_data_create();
+		if settings.is_null() {
+			return settings;
+		}
+		let time_key = std::ffi::CString::new("max_time_sec").unwrap();
+		revo_lib::obs::obs_data_set_int(settings, time_key.as_ptr(), max_seconds);
+
+		let size_key = std::ffi::CString::new("max_size_mb").unwrap();
+		revo_lib::obs::obs_data_set_int(settings, size_key.as_ptr(), max_megabytes);
+
+		let dir_key = std::ffi::CString::new("directory").unwrap();
+		let dir_val = std::ffi::CString::new(directory).unwrap_or_default();
+		revo_lib::obs::obs_data_set_string(settings, dir_key.as_ptr(), dir_val.as_ptr());
+
+		// libobs picks the file name; it is renamed via resolve_record_path after saving.
+		let format_key = std::ffi::CString::new("format").unwrap();
+		let format_val = std::ffi::CString::new("replay_%CCYY-%MM-%DD_%hh-%mm-%ss").unwrap();
+		revo_lib::obs::obs_data_set_string(settings, format_key.as_ptr(), format_val.as_ptr());
+
+		let ext_key = std::ffi::CString::new("extension").unwrap();
+		let ext_val = std::ffi::CString::new(extension).unwrap_or_default();
+		revo_lib::obs::obs_data_set_string(settings, ext_key.as_ptr(), ext_val.as_ptr());
+
+		let spaces_key = std::ffi::CString::new("allow_spaces").unwrap();
+		revo_lib::obs::obs_data_set_bool(settings, spaces_key.as_ptr(), false);
+
+		settings
+	}
+}
+
+// Reuses the recording or streaming encoders when one of them is running so
+// the replay buffer does not cost a second encode.
+fn acquire_encoders(
+	runtime: &crate::ObsRuntime,
+) -> Result<(*mut revo_lib::obs::obs_encoder, *mut revo_lib::obs::obs_encoder, bool), String> {
+	let candidates = [
+		(
+			runtime.record_video_encoder,
+			runtime.record_audio_encoders.first().copied().unwrap_or(std::ptr::null_mut()),
+		),
+		(runtime.stream_video_encoder, runtime.stream_audio_encoder),
+	];
+	for (video, audio) in candidates {
+		if video.is_null() || audio.is_null() {
+			continue;
+		}
+		unsafe {
+			let video_ref = revo_lib::obs::obs_encoder_get_ref(video);
+			let audio_ref = revo_lib::obs::obs_encoder_get_ref(audio);
+			if !video_ref.is_null() && !audio_ref.is_null() {
+				return Ok((video_ref, audio_ref, true));
+			}
+			if !video_ref.is_null() {
+				revo_lib::obs::obs_encoder_release(video_ref);
+			}
+			if !audio_ref.is_null() {
+				revo_lib::obs::obs_encoder_release(audio_ref);
+			}
+		}
+	}
+
+	// A fresh encoder follows the profile's streaming encoder settings, which
+	// are checked to be H.264.
+	super::encoders::ensure_video_format_supported("h264")?;
+	let settings = crate::settings::core::settings_get()?;
+	let encoder_settings = crate::settings::core::streaming_encoder_settings(&settings)?;
+	super::encoders::validate_streaming_video_encoder(
+		runtime.video_encoder_preference,
+		&encoder_settings,
+	)?;
+	let video_encoder =
+		super::encoders::create_video_encoder(runtime.video_encoder_preference, &encoder_settings);
+	let audio_encoder = super::encoders::create_audio_encoder();
+	if video_encoder.is_null() || audio_encoder.is_null() {
+		unsafe {
+			if !video_encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(video_encoder);
+			}
+			if !audio_encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(audio_encoder);
+			}
+		}
+		return Err("failed to create replay buffer encoders".to_string());
+	}
+	unsafe {
+		revo_lib::obs::obs_encoder_set_video(video_encoder, revo_lib::obs::obs_get_video());
+		revo_lib::obs::obs_encoder_set_audio(audio_encoder, revo_lib::obs::obs_get_audio());
+	}
+	Ok((video_encoder, audio_encoder, false))
+}
+
+pub(crate) fn start_replay_buffer(
+	state: tauri::State<crate::ObsState>,
+	output_path: String,
+	max_seconds: Option<i64>,
+	max_megabytes: Option<i64>,
+) -> Result<String, String> {
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	if runtime.replay_buffer.is_some() {
+		return Ok("Replay buffer already active".to_string());
+	}
+	if !super::encoders::output_type_available("replay_buffer") {
+		return Err("replay_buffer output is unavailable (obs-ffmpeg module not loaded)".to_string());
+	}
+
+	let max_seconds = max_seconds.unwrap_or(DEFAULT_REPLAY_MAX_SECONDS);
+	if !(1..=21_600).contains(&max_seconds) {
+		return Err("Replay buffer length must be between 1 and 21600 seconds".to_string());
+	}
+	let max_megabytes = max_megabytes.unwrap_or(DEFAULT_REPLAY_MAX_MEGABYTES);
+	if !(1..=65_536).contains(&max_megabytes) {
+		return Err("Replay buffer size must be between 1 and 65536 MB".to_string());
+	}
+
+	// Only used to derive directory/extension; the real name is resolved on save.
+	let template =
+		super::helpers::resolve_record_path(&output_path, runtime.current_scene.as_deref())?;
+	if let Some(dir) = template.parent() {
+		super::disk_watchdog::ensure_space_for_start(dir)?;
+	}
+	let settings = replay_output_settings(&template, max_seconds, max_megabytes);
+	let output = unsafe {
+		let id = std::ffi::CString::new("replay_buffer").unwrap();
+		let name = std::ffi::CString::new("revo_replay").unwrap();
+		let output =
+			revo_lib::obs::obs_output_create(id.as_ptr(), name.as_ptr(), settings, std::ptr::null_mut());
+		if !settings.is_null() {
+			revo_lib::obs::obs_data_release(settings);
+		}
+		output
+	};
+	if output.is_null() {
+		return Err("failed to create replay buffer output".to_string());
+	}
+
+	let (video_encoder, audio_encoder, shared_encoders) = match acquire_encoders(&runtime) {
+		Ok(encoders) => encoders,
+		Err(err) => {
+			unsafe { revo_lib::obs::obs_output_release(output) };
+			return Err(err);
+		}
+	};
+	unsafe {
+		revo_lib::obs::obs_output_set_video_encoder(output, video_encoder);
+		revo_lib::obs::obs_output_set_audio_encoder(output, audio_encoder, 0);
+	}
+
+	set_saved_signal(output, true);
+	super::output_state::attach_output_signals(super::output_state::REPLAY_LABEL, output);
+	if !unsafe { revo_lib::obs::obs_output_start(output) } {
+		let last_error = unsafe {
+			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
+		};
+		super::output_state::mark_start_failed(super::output_state::REPLAY_LABEL, &last_error);
+		set_saved_signal(output, false);
+		unsafe {
+			revo_lib::obs::obs_output_release(output);
+			revo_lib::obs::obs_encoder_release(video_encoder);
+			revo_lib::obs::obs_encoder_release(audio_encoder);
+		}
+		return Err(format!("replay buffer start failed: {last_error}"));
+	}
+
+	runtime.replay_buffer = Some(crate::ReplayBufferState {
+		output,
+		video_encoder,
+		audio_encoder,
+		output_path,
+	});
+	let disk_warning = template.parent().and_then(|dir| {
+		super::disk_watchdog::begin_watch(super::output_state::REPLAY_LABEL, dir)
+	});
+	Ok(format!(
+		"Replay buffer started ({max_seconds}s / {max_megabytes} MB{}{})",
+		if shared_encoders { ", shared encoders" } else { "" },
+		disk_warning.map(|w| format!("; warning: {w}")).unwrap_or_default()
+	))
+}
+
+pub(crate) fn stop_replay_buffer(state: tauri::State<crate::ObsState>) -> Result<String, String> {
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if runtime.replay_buffer.is_none() {
+		return Ok("Replay buffer not active".to_string());
+	}
+	stop_replay_buffer_internal(&mut runtime);
+	Ok("Replay buffer stopped".to_string())
+}
+
+pub(crate) fn stop_replay_buffer_internal(runtime: &mut crate::ObsRuntime) {
+	let Some(replay) = runtime.replay_buffer.take() else {
+		return;
+	};
+	super::disk_watchdog::end_watch(super::output_state::REPLAY_LABEL);
+	unsafe {
+		if !replay.output.is_null() {
+			revo_lib::obs::obs_output_stop(replay.output);
+			set_saved_signal(replay.output, false);
+			super::output_state::detach_output_signals(super::output_state::REPLAY_LABEL);
+			revo_lib::obs::obs_output_release(replay.output);
+		}
+		if !replay.video_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(replay.video_encoder);
+		}
+		if !replay.audio_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(replay.audio_encoder);
+		}
+	}
+}
+
+pub(crate) fn save_replay_buffer(state: tauri::State<crate::ObsState>) -> Result<String, String> {
+	// Hold our own output reference so the runtime lock is not held while
+	// libobs flushes the buffer to disk.
+	let (output, output_path, scene) = {
+		let runtime = state
+			.runtime
+			.lock()
+			.map_err(|_| "state poisoned".to_string())?;
+		let replay = runtime
+			.replay_buffer
+			.as_ref()
+			.ok_or_else(|| "Replay buffer not active".to_string())?;
+		if !unsafe { revo_lib::obs::obs_output_active(replay.output) } {
+			return Err("Replay buffer is not running".to_string());
+		}
+		let output = unsafe { revo_lib::obs::obs_output_get_ref(replay.output) };
+		if output.is_null() {
+			return Err("Replay buffer output is gone".to_string());
+		}
+		(output, replay.output_path.clone(), runtime.current_scene.clone())
+	};
+
+	let result = save_and_wait(output);
+	unsafe { revo_lib::obs::obs_output_release(output) };
+	let saved = result?;
+
+	let target = super::helpers::resolve_record_path(&output_path, scene.as_deref())?;
+	let target = target.with_extension(
+		saved
+			.extension()
+			.map(|e| e.to_string_lossy().to_string())
+			.unwrap_or_else(|| "mp4".to_string()),
+	);
+	if std::fs::rename(&saved, &target).is_err() {
+		std::fs::copy(&saved, &target).map_err(|e| {
+			format!(
+				"replay saved to {} but could not be moved to {}: {e}",
+				saved.to_string_lossy(),
+				target.to_string_lossy()
+			)
+		})?;
+		let _ = std::fs::remove_file(&saved);
+	}
+	Ok(target.to_string_lossy().to_string())
+}
+
+fn save_and_wait(output: *mut revo_lib::obs::obs_output) -> Result<std::path::PathBuf, String> {
+	let (lock, cvar) = replay_saves();
+	let before = *lock.lock().map_err(|_| "replay state poisoned".to_string())?;
+
+	if call_output_proc(output, "save").is_none() {
+		return Err("replay buffer does not support saving".to_string());
+	}
+
+	let guard = lock.lock().map_err(|_| "replay state poisoned".to_string())?;
+	let (_guard, timeout) = cvar
+		.wait_timeout_while(guard, REPLAY_SAVE_TIMEOUT, |count| *count == before)
+		.map_err(|_| "replay state poisoned".to_string())?;
+	if timeout.timed_out() {
+		return Err("timed out waiting for the replay to be saved".to_string());
+	}
+
+	let path = call_output_proc(output, "get_last_replay").unwrap_or_default();
+	if path.is_empty() {
+		return Err("replay saved but libobs did not report a path".to_string());
+	}
+	Ok(std::path::PathBuf::from(path))
+}
@@ -10,6 +10,14 @@ pub(crate) fn is_initialized(state: tauri::State<crate::ObsState>) -> Result<boo
 	Ok(runtime.initialized)
 }
 
+pub(crate) fn is_offscreen(state: tauri::State<crate::ObsState>) -> Result<bool, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	Ok(runtime.initialized && super::headless::is_offscreen())
+}
+
 pub(crate) fn version() -> Result<String, String> {
 	unsafe {
 		let ptr = revo_lib::obs::obs_get_version_string();
@@ -0,0 +1,133 @@
+// Output and render statistics, with bitrate computed over a sliding window.
+
+const BITRATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(5);
+
+static BYTE_SAMPLES: std::sync::OnceLock<
+	std::sync::Mutex<
+		std::collections::HashMap<String, std::collections::VecDeque<(std::time::Instant, u64)>>,
+	>,
+> = std::sync::OnceLock::new();
+
+fn byte_samples() -> &'static std::sync::Mutex<
+	std::collections::HashMap<String, std::collections::VecDeque<(std::time::Instant, u64)>>,
+> {
+	BYTE_SAMPLES.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+fn sample_kbps(label: &str, total_bytes: u64) -> f64 {
+	let Ok(mut samples) = byte_samples().lock() else {
+		return 0.0;
+	};
+	let now = std::time::Instant::now();
+	let window = samples.entry(label.to_string()).or_default();
+	// A byte counter going backwards means the output was recreated.
+	if window.back().is_some_and(|(_, bytes)| *bytes > total_bytes) {
+		window.clear();
+	}
+	window.push_back((now, total_bytes));
+	while window.len() > 2
+		&& window
+			.get(1)
+			.is_some_and(|(at, _)| now.duration_since(*at) >= BITRATE_WINDOW)
+	{
+		window.pop_front();
+	}
+
+	let (Some((first_at, first_bytes)), Some((last_at, last_bytes))) = (window.front(), window.back())
+	else {
+		return 0.0;
+	};
+	let elapsed = last_at.duration_since(*first_at).as_secs_f64();
+	if elapsed <= 0.0 {
+		return 0.0;
+	}
+	(last_bytes.saturating_sub(*first_bytes) as f64 * 8.0 / 1000.0) / elapsed
+}
+
+fn collect_output_stats(label: &str, output: *mut revo_lib::obs::obs_output) -> crate::OutputStats {
+	unsafe {
+		let active = revo_lib::obs::obs_output_active(output);
+		let total_bytes = revo_lib::obs::obs_output_get_total_bytes(output);
+		let total_frames = revo_lib::obs::obs_output_get_total_frames(output);
+		let frames_dropped = revo_lib::obs::obs_output_get_frames_dropped(output);
+		let dropped_percent = if total_frames > 0 {
+			frames_dropped as f64 * 100.0 / total_frames as f64
+		} else {
+			0.0
+		};
+		crate::OutputStats {
+			label: label.to_string(),
+			active,
+			total_bytes,
+			total_frames,
+			frames_dropped,
+			dropped_percent,
+			congestion: revo_lib::obs::obs_output_get_congestion(output),
+			connect_time_ms: revo_lib::obs::obs_output_get_connect_time_ms(output),
+			kbps: if active { sample_kbps(label, total_bytes) } else { 0.0 },
+		}
+	}
+}
+
+fn collect_render_stats() -> crate::RenderStats {
+	unsafe {
+		let total_frames = revo_lib::obs::obs_get_total_frames();
+		let lagged_frames = revo_lib::obs::obs_get_lagged_frames();
+		crate::RenderStats {
+			total_frames,
+			lagged_frames,
+			lagged_percent: if total_frames > 0 {
+				lagged_frames as f64 * 100.0 / total_frames as f64
+			} else {
+				0.0
+			},
+			average_frame_time_ms: revo_lib::obs::obs_get_average_frame_time_ns() as f64 / 1_000_000.0,
+		}
+	}
+}
+
+pub(crate) fn get_output_stats(
+	state: tauri::State<crate::ObsState>,
+) -> Result<crate::ObsOutputStats, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+
+	let mut tracked: Vec<(String, *mut revo_lib::obs::obs_output)> = Vec::new();
+	if !runtime.output_stream.is_null() {
+		tracked.push((super::output_state::STREAM_LABEL.to_string(), runtime.output_stream));
+	}
+	if !runtime.output_record.is_null() {
+		tracked.push((super::output_state::RECORD_LABEL.to_string(), runtime.output_record));
+	}
+	if let Some(replay) = runtime.replay_buffer.as_ref() {
+		tracked.push((super::output_state::REPLAY_LABEL.to_string(), replay.output));
+	}
+	let mut target_names: Vec<&String> = runtime.stream_targets.keys().collect();
+	target_names.sort();
+	for name in target_names {
+		if let Some(target) = runtime.stream_targets.get(name) {
+			if !target.output.is_null() {
+				tracked.push((super::output_state::stream_target_label(name), target.output));
+			}
+		}
+	}
+
+	let outputs: Vec<crate::OutputStats> = tracked
+		.iter()
+		.map(|(label, output)| collect_output_stats(label, *output))
+		.collect();
+
+	if let Ok(mut samples) = byte_samples().lock() {
+		samples.retain(|label, _| tracked.iter().any(|(l, _)| l == label));
+	}
+
+	Ok(crate::ObsOutputStats {
+		outputs,
+		render: collect_render_stats(),
+	})
+}
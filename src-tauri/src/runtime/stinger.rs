@@ -0,0 +1,144 @@
+// Stinger media for obs_stinger_transition. Image sequences are rendered once
+// into an alpha-preserving MOV (PNG frames) under data/stingers and reused
+// until the folder or frame rate changes.
+
+use std::hash::{Hash, Hasher};
+
+const STINGER_CACHE_DIR: &str = "stingers";
+
+// Ids are free-form, so the readable part is sanitized and a hash of the raw
+// id keeps ids that sanitize alike ("a b" / "a_b") apart.
+fn cache_prefix(transition_id: &str) -> String {
+	let safe: String = transition_id
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+		.collect();
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	transition_id.hash(&mut hasher);
+	format!("{safe}-{:08x}-", hasher.finish() as u32)
+}
+
+// Exactly `{prefix}{16 hex digits}.mov`; a bare prefix match would also catch
+// renders of ids that merely start with this one ("intro" vs "intro-2").
+fn is_render_of(name: &str, prefix: &str) -> bool {
+	name.strip_prefix(prefix)
+		.and_then(|rest| rest.strip_suffix(".mov"))
+		.is_some_and(|hash| hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// Covers frame names, sizes and mtimes so edits to the folder re-render.
+fn sequence_fingerprint(frames: &[std::path::PathBuf], fps: u32) -> u64 {
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	fps.hash(&mut hasher);
+	for frame in frames {
+		frame.hash(&mut hasher);
+		if let Ok(meta) = std::fs::metadata(frame) {
+			meta.len().hash(&mut hasher);
+			if let Ok(modified) = meta.modified() {
+				modified.hash(&mut hasher);
+			}
+		}
+	}
+	hasher.finish()
+}
+
+fn concat_list(frames: &[std::path::PathBuf], fps: u32) -> String {
+	let quote = |p: &std::path::Path| p.to_string_lossy().replace('\'', "'\\''");
+	let mut out = String::from("ffconcat version 1.0\n");
+	for frame in frames {
+		out.push_str(&format!("file '{}'\nduration {:.6}\n", quote(frame), 1.0 / fps as f64));
+	}
+	// The concat demuxer ignores the last duration unless the file repeats.
+	if let Some(last) = frames.last() {
+		out.push_str(&format!("file '{}'\n", quote(last)));
+	}
+	out
+}
+
+fn render_sequence(
+	frames: &[std::path::PathBuf],
+	fps: u32,
+	target: &std::path::Path,
+) -> Result<(), String> {
+	let list = target.with_extension("txt");
+	let temp = target.with_extension("render-tmp.mov");
+	std::fs::write(&list, concat_list(frames, fps))
+		.map_err(|e| format!("failed to write stinger frame list: {e}"))?;
+	let output = std::process::Command::new(super::remux::ffmpeg_bin())
+		.args(["-hide_banner", "-loglevel", "error", "-nostats", "-y"])
+		.args(["-f", "concat", "-safe", "0", "-i"])
+		.arg(&list)
+		.args(["-r", &fps.to_string(), "-c:v", "png", "-pix_fmt", "rgba", "-f", "mov"])
+		.arg(&temp)
+		.stdin(std::process::Stdio::null())
+		.output();
+	let _ = std::fs::remove_file(&list);
+	let output = output.map_err(|e| format!("failed to run ffmpeg: {e}"))?;
+	if !output.status.success() {
+		let _ = std::fs::remove_file(&temp);
+		let detail = String::from_utf8_lossy(&output.stderr).trim().to_string();
+		return Err(if detail.is_empty() {
+			format!("ffmpeg exited with {}", output.status)
+		} else {
+			format!("failed to render stinger sequence: {detail}")
+		});
+	}
+	std::fs::rename(&temp, target).map_err(|e| format!("failed to finalize stinger: {e}"))
+}
+
+// Older renders of the same transition are dropped once a new one exists.
+fn prune_renders(dir: &std::path::Path, prefix: &str, keep: &std::path::Path) {
+	let Ok(entries) = std::fs::read_dir(dir) else {
+		return;
+	};
+	for entry in entries.flatten() {
+		let path = entry.path();
+		let name = entry.file_name().to_string_lossy().to_string();
+		if is_render_of(&name, prefix) && path != keep {
+			let _ = std::fs::remove_file(path);
+		}
+	}
+}
+
+/// Media file for a sequence stinger, rendering it when the cache is stale.
+pub(crate) fn sequence_media(
+	transition_id: &str,
+	dir: &std::path::Path,
+	fps: u32,
+) -> Result<std::path::PathBuf, String> {
+	let frames = crate::settings::themes::sequence_frames(dir)?;
+	if frames
+		.iter()
+		.any(|f| f.extension().is_some_and(|e| e.eq_ignore_ascii_case("svg")))
+	{
+		return Err("SVG frames cannot be played as a stinger; export the sequence as PNG".to_string());
+	}
+	let cache_dir = crate::settings::core::runtime_data_dir()?.join(STINGER_CACHE_DIR);
+	std::fs::create_dir_all(&cache_dir)
+		.map_err(|e| format!("failed to create stinger cache: {e}"))?;
+	let prefix = cache_prefix(transition_id);
+	let target = cache_dir.join(format!("{prefix}{:016x}.mov", sequence_fingerprint(&frames, fps)));
+	if !target.is_file() {
+		render_sequence(&frames, fps, &target)?;
+		prune_renders(&cache_dir, &prefix, &target);
+	}
+	Ok(target)
+}
+
+/// The file obs_stinger_transition plays for `config`.
+pub(crate) fn stinger_media(
+	config: &crate::settings::core::TransitionSettings,
+	stinger: &crate::settings::core::StingerSettings,
+) -> Result<std::path::PathBuf, String> {
+	match &stinger.source {
+		crate::settings::core::StingerSource::Media(path) => {
+			if !path.is_file() {
+				return Err(format!("stinger file not found: {}", path.to_string_lossy()));
+			}
+			Ok(path.clone())
+		}
+		crate::settings::core::StingerSource::Sequence { dir, fps } => {
+			sequence_media(&config.id, dir, *fps)
+		}
+	}
+}
@@ -0,0 +1,527 @@
+// Named stream destinations that run alongside the primary stream output.
+
+fn target_protocol(target: &crate::StreamTargetConfig) -> crate::StreamProtocol {
+	target
+		.protocol
+		.or_else(|| super::encoders::stream_protocol_from_url(&target.url))
+		.unwrap_or(crate::StreamProtocol::Rtmp)
+}
+
+fn target_service_name(target: &crate::StreamTargetConfig) -> Option<&str> {
+	target.service.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn live_video_info() -> Option<(u32, u32, f64)> {
+	unsafe {
+		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
+		if !revo_lib::obs::obs_get_video_info(&mut ovi as *mut _) || ovi.fps_den == 0 {
+			return None;
+		}
+		Some((
+			ovi.output_width,
+			ovi.output_height,
+			ovi.fps_num as f64 / ovi.fps_den as f64,
+		))
+	}
+}
+
+/// Service recommendation check shared by the primary stream and targets;
+/// `overrides` are a target's per-output bitrates.
+pub(crate) fn service_warnings(
+	service: &crate::RtmpService,
+	overrides: Option<&crate::StreamTargetEncoder>,
+	protocol: crate::StreamProtocol,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> Vec<String> {
+	let overrides = overrides.cloned().unwrap_or_default();
+	let video = live_video_info();
+	crate::settings::services::service_recommendation_warnings(
+		service,
+		&crate::settings::services::ServiceCheckInput {
+			video_codec: "h264",
+			audio_codec: if protocol == crate::StreamProtocol::Whip { "opus" } else { "aac" },
+			video_bitrate: overrides
+				.video_bitrate
+				.filter(|b| *b > 0)
+				.unwrap_or(encoder_settings.bitrate),
+			audio_bitrate: overrides.audio_bitrate.filter(|b| *b > 0).unwrap_or(160),
+			keyint_sec: encoder_settings.keyint_sec,
+			bframes: encoder_settings.low_latency.then_some(0),
+			profile: encoder_settings.profile.as_deref(),
+			fps: video.map(|(_, _, fps)| fps),
+			output_resolution: video.map(|(w, h, _)| (w, h)),
+		},
+	)
+}
+
+fn target_connect_url(target: &crate::StreamTargetConfig, protocol: crate::StreamProtocol) -> String {
+	let url = target.url.trim();
+	let key = target.key.trim();
+	if key.is_empty() {
+		return url.to_string();
+	}
+	match protocol {
+		crate::StreamProtocol::Rtmp => format!("{}/{}", url.trim_end_matches('/'), key),
+		crate::StreamProtocol::Srt if !url.contains("streamid=") => {
+			let sep = if url.contains('?') { '&' } else { '?' };
+			format!("{url}{sep}streamid={key}")
+		}
+		_ => url.to_string(),
+	}
+}
+
+// Outputs with the same signature (the primary stream included) can feed from
+// the same encoder pair.
+pub(crate) fn encoder_signature(
+	protocol: crate::StreamProtocol,
+	preference: crate::VideoEncoderPreference,
+	audio_bitrate: Option<i64>,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> String {
+	let webrtc = protocol == crate::StreamProtocol::Whip;
+	format!(
+		"{}|{}|{}|{:?}",
+		if webrtc { "webrtc" } else { "default" },
+		match preference {
+			crate::VideoEncoderPreference::Hardware => "hardware",
+			crate::VideoEncoderPreference::Software => "software",
+		},
+		audio_bitrate.unwrap_or(if webrtc { 128 } else { 160 }),
+		encoder_settings
+	)
+}
+
+fn target_encoder_signature(
+	target: &crate::StreamTargetConfig,
+	protocol: crate::StreamProtocol,
+	preference: crate::VideoEncoderPreference,
+	encoder_settings: &crate::settings::core::StreamingEncoderSettings,
+) -> String {
+	let overrides = target.encoder.clone().unwrap_or_default();
+	let mut effective = encoder_settings.clone();
+	if let Some(bitrate) = overrides.video_bitrate.filter(|b| *b > 0) {
+		effective.bitrate = bitrate;
+	}
+	encoder_signature(
+		protocol,
+		preference,
+		overrides.audio_bitrate.filter(|b| *b > 0),
+		&effective,
+	)
+}
+
+fn target_encoder_preference(
+	target: &crate::StreamTargetConfig,
+	fallback: crate::VideoEncoderPreference,
+) -> crate::VideoEncoderPreference {
+	match target
+		.encoder
+		.as_ref()
+		.and_then(|e| e.encoder_preference.as_deref())
+		.map(|v| v.trim().to_lowercase())
+		.as_deref()
+	{
+		Some("software") | Some("x264") | Some("obs_x264") => crate::VideoEncoderPreference::Software,
+		Some("hardware") => crate::VideoEncoderPreference::Hardware,
+		_ => fallback,
+	}
+}
+
+fn apply_encoder_overrides(
+	target: &crate::StreamTargetConfig,
+	video_encoder: *mut revo_lib::obs::obs_encoder,
+	audio_encoder: *mut revo_lib::obs::obs_encoder,
+) {
+	let Some(encoder) = target.encoder.as_ref() else {
+		return;
+	};
+	unsafe {
+		for (enc, bitrate) in [
+			(video_encoder, encoder.video_bitrate),
+			(audio_encoder, encoder.audio_bitrate),
+		] {
+			let Some(bitrate) = bitrate.filter(|b| *b > 0) else {
+				continue;
+			};
+			if enc.is_null() {
+				continue;
+			}
+			let settings = revo_lib::obs::obs_data_create();
+			if settings.is_null() {
+				continue;
+			}
+			let key = std::ffi::CString::new("bitrate").unwrap();
+			revo_lib::obs::obs_data_set_int(settings, key.as_ptr(), bitrate);
+			revo_lib::obs::obs_encoder_update(enc, settings);
+			revo_lib::obs::obs_data_release(settings);
+		}
+	}
+}
+
+fn configured_targets() -> Result<Vec<crate::StreamTargetConfig>, String> {
+	Ok(crate::settings::core::settings_get()?
+		.stream_targets
+		.unwrap_or_default())
+}
+
+fn find_target(name: &str) -> Result<crate::StreamTargetConfig, String> {
+	configured_targets()?
+		.into_iter()
+		.find(|t| t.name == name)
+		.ok_or_else(|| format!("stream target not found: {name}"))
+}
+
+pub(crate) fn list_stream_targets(
+	state: tauri::State<crate::ObsState>,
+) -> Result<Vec<crate::StreamTargetInfo>, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	let targets = configured_targets()?;
+	Ok(targets
+		.iter()
+		.map(|target| {
+			let protocol = target_protocol(target);
+			let active_state = runtime.stream_targets.get(&target.name);
+			let active = active_state
+				.map(|s| unsafe { revo_lib::obs::obs_output_active(s.output) })
+				.unwrap_or(false);
+			crate::StreamTargetInfo {
+				name: target.name.clone(),
+				url: target.url.clone(),
+				protocol,
+				service: target_service_name(target).map(str::to_string),
+				has_custom_encoder: target.encoder.as_ref().is_some_and(|e| *e != crate::StreamTargetEncoder::default()),
+				active,
+				shared_encoders: active_state.map(|s| s.shared_encoders).unwrap_or(false),
+				state: super::output_state::output_state(&super::output_state::stream_target_label(
+					&target.name,
+				)),
+			}
+		})
+		.collect())
+}
+
+pub(crate) fn save_stream_target(
+	state: tauri::State<crate::ObsState>,
+	target: crate::StreamTargetConfig,
+) -> Result<String, String> {
+	let name = target.name.trim().to_string();
+	if name.is_empty() {
+		return Err("stream target name required".to_string());
+	}
+	if let Some(service_name) = target_service_name(&target) {
+		let service = crate::settings::services::find_rtmp_service(service_name)?;
+		crate::settings::services::resolve_service_server(&service, target.server.as_deref())?;
+	} else if target.url.trim().is_empty() {
+		return Err("stream target URL or service required".to_string());
+	}
+	{
+		let runtime = state
+			.runtime
+			.lock()
+			.map_err(|_| "state poisoned".to_string())?;
+		if runtime.stream_targets.contains_key(&name) {
+			return Err("Cannot edit a stream target while it is live".to_string());
+		}
+	}
+
+	let mut settings = crate::settings::core::settings_get()?;
+	let mut targets = settings.stream_targets.take().unwrap_or_default();
+	let mut next = target;
+	next.name = name.clone();
+	if let Some(existing) = targets.iter_mut().find(|t| t.name == name) {
+		*existing = next;
+	} else {
+		targets.push(next);
+	}
+	settings.stream_targets = Some(targets);
+	crate::settings::core::settings_save(settings)?;
+	Ok(format!("Stream target saved: {name}"))
+}
+
+pub(crate) fn remove_stream_target(
+	state: tauri::State<crate::ObsState>,
+	name: String,
+) -> Result<String, String> {
+	let name = name.trim().to_string();
+	{
+		let mut runtime = state
+			.runtime
+			.lock()
+			.map_err(|_| "state poisoned".to_string())?;
+		stop_stream_target_internal(&mut runtime, &name);
+	}
+
+	let mut settings = crate::settings::core::settings_get()?;
+	let mut targets = settings.stream_targets.take().unwrap_or_default();
+	let before = targets.len();
+	targets.retain(|t| t.name != name);
+	if targets.len() == before {
+		return Err(format!("stream target not found: {name}"));
+	}
+	settings.stream_targets = Some(targets);
+	crate::settings::core::settings_save(settings)?;
+	Ok(format!("Stream target removed: {name}"))
+}
+
+pub(crate) fn start_stream_target(
+	state: tauri::State<crate::ObsState>,
+	name: String,
+) -> Result<String, String> {
+	let name = name.trim().to_string();
+	let mut target = find_target(&name)?;
+	let settings = crate::settings::core::settings_get()?;
+	let mut encoder_settings = crate::settings::core::streaming_encoder_settings(&settings)?;
+	// A per-target encoder preference overrides the profile's encoder choice.
+	if target
+		.encoder
+		.as_ref()
+		.is_some_and(|e| e.encoder_preference.is_some())
+	{
+		encoder_settings.video_encoder = None;
+	}
+	let reconnect = crate::settings::core::stream_reconnect_settings(&settings)?;
+	let catalog_service = match target_service_name(&target) {
+		Some(service_name) => {
+			let service = crate::settings::services::find_rtmp_service(service_name)?;
+			let server =
+				crate::settings::services::resolve_service_server(&service, target.server.as_deref())?;
+			target.url = server.url.clone();
+			Some(service)
+		}
+		None => None,
+	};
+
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	if runtime.stream_targets.contains_key(&name) {
+		if !super::output_state::output_failed(&super::output_state::stream_target_label(&name)) {
+			return Ok(format!("Stream target already active: {name}"));
+		}
+		stop_stream_target_internal(&mut runtime, &name);
+	}
+	super::encoders::ensure_video_format_supported("h264")?;
+
+	let protocol = target_protocol(&target);
+	let url = target_connect_url(&target, protocol);
+	let whip_bearer_token = if protocol == crate::StreamProtocol::Whip {
+		if target.key.trim().is_empty() {
+			settings.whip_bearer_token.clone().unwrap_or_default()
+		} else {
+			target.key.trim().to_string()
+		}
+	} else {
+		String::new()
+	};
+	let preference = target_encoder_preference(&target, runtime.video_encoder_preference);
+	if protocol != crate::StreamProtocol::Whip {
+		super::encoders::validate_streaming_video_encoder(preference, &encoder_settings)?;
+	}
+	let signature = target_encoder_signature(&target, protocol, preference, &encoder_settings);
+
+	// A running pair with the same signature is handed to the output builders,
+	// which then skip creating encoders of their own.
+	let shared = runtime
+		.stream_targets
+		.values()
+		.filter(|s| s.encoder_signature == signature)
+		.map(|s| (s.video_encoder, s.audio_encoder))
+		.chain(
+			(runtime.stream_encoder_signature == signature)
+				.then_some((runtime.stream_video_encoder, runtime.stream_audio_encoder)),
+		)
+		.find(|(video, audio)| !video.is_null() && !audio.is_null());
+
+	let mut service = std::ptr::null_mut();
+	let mut video_encoder = std::ptr::null_mut();
+	let mut audio_encoder = std::ptr::null_mut();
+	let mut shared_encoders = false;
+	if let Some((shared_video, shared_audio)) = shared {
+		unsafe {
+			let video_ref = revo_lib::obs::obs_encoder_get_ref(shared_video);
+			let audio_ref = revo_lib::obs::obs_encoder_get_ref(shared_audio);
+			if !video_ref.is_null() && !audio_ref.is_null() {
+				video_encoder = video_ref;
+				audio_encoder = audio_ref;
+				shared_encoders = true;
+			} else {
+				if !video_ref.is_null() {
+					revo_lib::obs::obs_encoder_release(video_ref);
+				}
+				if !audio_ref.is_null() {
+					revo_lib::obs::obs_encoder_release(audio_ref);
+				}
+			}
+		}
+	}
+	let output = match catalog_service.as_ref() {
+		Some(catalog) if protocol == crate::StreamProtocol::Rtmp => {
+			super::encoders::create_rtmp_common_stream_output(
+				&catalog.name,
+				&target.url,
+				target.key.trim(),
+				&mut service,
+				&mut video_encoder,
+				&mut audio_encoder,
+				preference,
+				&encoder_settings,
+			)
+		}
+		_ => super::encoders::create_stream_output_for_url(
+			&url,
+			protocol,
+			&whip_bearer_token,
+			&mut service,
+			&mut video_encoder,
+			&mut audio_encoder,
+			preference,
+			&encoder_settings,
+		),
+	};
+	let output = match output {
+		Ok(output) => output,
+		Err(err) => {
+			// Only shared references can be left behind by a failed build.
+			release_target_handles(
+				std::ptr::null_mut(),
+				std::ptr::null_mut(),
+				video_encoder,
+				audio_encoder,
+			);
+			return Err(err);
+		}
+	};
+	// The ffmpeg_output fallback hands shared references back.
+	let shared_encoders = shared_encoders && !video_encoder.is_null();
+	let mut warnings = super::encoders::video_encoder_warnings(video_encoder, &encoder_settings);
+	if let Some(catalog) = catalog_service.as_ref() {
+		warnings.extend(service_warnings(
+			catalog,
+			target.encoder.as_ref(),
+			protocol,
+			&encoder_settings,
+		));
+	}
+
+	if !shared_encoders {
+		apply_encoder_overrides(&target, video_encoder, audio_encoder);
+	}
+
+	let flags = unsafe { revo_lib::obs::obs_output_get_flags(output) };
+	if (flags & revo_lib::obs::OBS_OUTPUT_ENCODED) == 0 {
+		unsafe {
+			revo_lib::obs::obs_output_set_media(
+				output,
+				revo_lib::obs::obs_get_video(),
+				revo_lib::obs::obs_get_audio(),
+			)
+		};
+	}
+
+	let label = super::output_state::stream_target_label(&name);
+	super::output_state::configure_stream_reconnect(output, reconnect);
+	super::output_state::attach_output_signals(&label, output);
+	let started = revo_lib::streaming::StreamingOutput::from_raw(output)
+		.and_then(|o| o.start())
+		.is_ok();
+	if !started {
+		let last_error = unsafe {
+			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
+		};
+		super::output_state::mark_start_failed(&label, &last_error);
+		release_target_handles(output, service, video_encoder, audio_encoder);
+		return Err(format!("stream target '{name}' failed to start: {last_error}"));
+	}
+
+	runtime.stream_targets.insert(
+		name.clone(),
+		crate::StreamTargetState {
+			output,
+			service,
+			video_encoder,
+			audio_encoder,
+			encoder_signature: signature,
+			shared_encoders,
+		},
+	);
+	if warnings.is_empty() {
+		return Ok(format!("Stream target started: {name} ({})", protocol.label()));
+	}
+	crate::logging::debug::push_debug_log_entry(
+		format!("stream_target_warnings:{name}"),
+		Some(serde_json::json!({ "warnings": warnings })),
+	);
+	Ok(format!(
+		"Stream target started: {name} ({}) (warnings: {})",
+		protocol.label(),
+		warnings.join("; ")
+	))
+}
+
+pub(crate) fn stop_stream_target(
+	state: tauri::State<crate::ObsState>,
+	name: String,
+) -> Result<String, String> {
+	let name = name.trim().to_string();
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !stop_stream_target_internal(&mut runtime, &name) {
+		return Ok(format!("Stream target not active: {name}"));
+	}
+	Ok(format!("Stream target stopped: {name}"))
+}
+
+pub(crate) fn stop_stream_target_internal(runtime: &mut crate::ObsRuntime, name: &str) -> bool {
+	let Some(target) = runtime.stream_targets.remove(name) else {
+		return false;
+	};
+	if let Ok(output) = revo_lib::streaming::StreamingOutput::from_raw(target.output) {
+		output.stop();
+	}
+	super::output_state::detach_output_signals(&super::output_state::stream_target_label(name));
+	release_target_handles(
+		target.output,
+		target.service,
+		target.video_encoder,
+		target.audio_encoder,
+	);
+	true
+}
+
+pub(crate) fn stop_all_stream_targets_internal(runtime: &mut crate::ObsRuntime) {
+	let names: Vec<String> = runtime.stream_targets.keys().cloned().collect();
+	for name in names {
+		stop_stream_target_internal(runtime, &name);
+	}
+}
+
+fn release_target_handles(
+	output: *mut revo_lib::obs::obs_output,
+	service: *mut revo_lib::obs::obs_service,
+	video_encoder: *mut revo_lib::obs::obs_encoder,
+	audio_encoder: *mut revo_lib::obs::obs_encoder,
+) {
+	unsafe {
+		if !output.is_null() {
+			revo_lib::obs::obs_output_release(output);
+		}
+		if !service.is_null() {
+			revo_lib::obs::obs_service_release(service);
+		}
+		if !video_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(video_encoder);
+		}
+		if !audio_encoder.is_null() {
+			revo_lib::obs::obs_encoder_release(audio_encoder);
+		}
+	}
+}
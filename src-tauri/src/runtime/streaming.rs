@@ -1,6 +1,58 @@
 pub(crate) fn start_streaming(
 	state: tauri::State<crate::ObsState>,
 	stream_url: String,
+) -> Result<String, String> {
+	let settings = crate::settings::core::settings_get().ok();
+	if settings
+		.as_ref()
+		.is_some_and(crate::settings::core::whip_streaming_selected)
+	{
+		return start_whip_streaming(state);
+	}
+	let url = stream_url.trim();
+	let protocol = match super::encoders::stream_protocol_from_url(url) {
+		Some(crate::StreamProtocol::Whip) => {
+			return Err(
+				"http(s) stream URLs are WHIP endpoints; set the WHIP URL and switch the streaming mode to WHIP"
+					.to_string(),
+			)
+		}
+		Some(protocol) => protocol,
+		None if !url.contains("://") => crate::StreamProtocol::Rtmp,
+		None => return Err(format!("unsupported stream URL scheme: {url}")),
+	};
+	start_primary_stream(state, settings.as_ref(), url, protocol, "")
+}
+
+/// Pushes the primary stream to the configured `whip_url`.
+pub(crate) fn start_whip_streaming(state: tauri::State<crate::ObsState>) -> Result<String, String> {
+	let settings = crate::settings::core::settings_get().ok();
+	let whip_url = settings
+		.as_ref()
+		.and_then(|s| s.whip_url.as_deref())
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.ok_or_else(|| "WHIP URL is not configured".to_string())?
+		.to_string();
+	let whip_bearer_token = settings
+		.as_ref()
+		.and_then(|s| s.whip_bearer_token.clone())
+		.unwrap_or_default();
+	start_primary_stream(
+		state,
+		settings.as_ref(),
+		&whip_url,
+		crate::StreamProtocol::Whip,
+		&whip_bearer_token,
+	)
+}
+
+fn start_primary_stream(
+	state: tauri::State<crate::ObsState>,
+	settings: Option<&crate::AppSettings>,
+	url: &str,
+	protocol: crate::StreamProtocol,
+	whip_bearer_token: &str,
 ) -> Result<String, String> {
 	let mut runtime = state
 		.runtime
@@ -10,28 +62,81 @@ pub(crate) fn start_streaming(
 		return Err("OBS not initialized".to_string());
 	}
 	if !runtime.output_stream.is_null() {
-		return Ok("Streaming already active".to_string());
+		if !super::output_state::output_failed(super::output_state::STREAM_LABEL) {
+			return Ok("Streaming already active".to_string());
+		}
+		// The failed output may not have been released yet.
+		super::helpers::stop_streaming_internal(&mut runtime);
+	}
+	// Every streaming encoder is H.264.
+	super::encoders::ensure_video_format_supported("h264")?;
+
+	let (encoder_settings, reconnect) = match settings {
+		Some(settings) => (
+			crate::settings::core::streaming_encoder_settings(settings)?,
+			crate::settings::core::stream_reconnect_settings(settings)?,
+		),
+		None => Default::default(),
+	};
+	// WHIP builds its own WebRTC-constrained encoder.
+	if protocol != crate::StreamProtocol::Whip {
+		super::encoders::validate_streaming_video_encoder(
+			runtime.video_encoder_preference,
+			&encoder_settings,
+		)?;
 	}
 
 	let mut service = std::ptr::null_mut();
 	let mut video_encoder = std::ptr::null_mut();
 	let mut audio_encoder = std::ptr::null_mut();
-	let output = super::encoders::create_rtmp_stream_output(
-		&stream_url,
+	let output = super::encoders::create_stream_output_for_url(
+		url,
+		protocol,
+		whip_bearer_token,
 		&mut service,
 		&mut video_encoder,
 		&mut audio_encoder,
 		runtime.video_encoder_preference,
+		&encoder_settings,
+	)?;
+	let mut warnings = super::encoders::video_encoder_warnings(video_encoder, &encoder_settings);
+	if protocol != crate::StreamProtocol::Whip {
+		if let Some(catalog) = crate::settings::services::find_rtmp_service_for_url(url) {
+			warnings.extend(super::stream_targets::service_warnings(
+				&catalog,
+				None,
+				protocol,
+				&encoder_settings,
+			));
+		}
+	}
+
+	start_stream_output(&mut runtime, output, service, video_encoder, audio_encoder, reconnect)?;
+	runtime.stream_encoder_signature = super::stream_targets::encoder_signature(
+		protocol,
+		runtime.video_encoder_preference,
+		None,
+		&encoder_settings,
 	);
-	let output = if output.is_null() {
-		super::encoders::create_ffmpeg_stream_output(&stream_url)
+	if warnings.is_empty() {
+		Ok("Streaming started".to_string())
 	} else {
-		output
-	};
-	if output.is_null() {
-		return Err("failed to create stream output".to_string());
+		crate::logging::debug::push_debug_log_entry(
+			"streaming_encoder_warnings".to_string(),
+			Some(serde_json::json!({ "warnings": warnings })),
+		);
+		Ok(format!("Streaming started (warnings: {})", warnings.join("; ")))
 	}
+}
 
+fn start_stream_output(
+	runtime: &mut crate::ObsRuntime,
+	output: *mut revo_lib::obs::obs_output,
+	service: *mut revo_lib::obs::obs_service,
+	video_encoder: *mut revo_lib::obs::obs_encoder,
+	audio_encoder: *mut revo_lib::obs::obs_encoder,
+	reconnect: crate::settings::core::StreamReconnectSettings,
+) -> Result<(), String> {
 	let flags = unsafe { revo_lib::obs::obs_output_get_flags(output) };
 	if (flags & revo_lib::obs::OBS_OUTPUT_ENCODED) == 0 {
 		unsafe {
@@ -43,6 +148,8 @@ pub(crate) fn start_streaming(
 		};
 	}
 
+	super::output_state::configure_stream_reconnect(output, reconnect);
+	super::output_state::attach_output_signals(super::output_state::STREAM_LABEL, output);
 	let started = revo_lib::streaming::StreamingOutput::from_raw(output)
 		.and_then(|o| o.start())
 		.is_ok();
@@ -50,7 +157,19 @@ pub(crate) fn start_streaming(
 		let last_error = unsafe {
 			super::helpers::cstr_to_string(revo_lib::obs::obs_output_get_last_error(output))
 		};
-		unsafe { revo_lib::obs::obs_output_release(output) };
+		super::output_state::mark_start_failed(super::output_state::STREAM_LABEL, &last_error);
+		unsafe {
+			revo_lib::obs::obs_output_release(output);
+			if !service.is_null() {
+				revo_lib::obs::obs_service_release(service);
+			}
+			if !video_encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(video_encoder);
+			}
+			if !audio_encoder.is_null() {
+				revo_lib::obs::obs_encoder_release(audio_encoder);
+			}
+		}
 		return Err(format!("stream start failed: {last_error}"));
 	}
 
@@ -58,7 +177,7 @@ pub(crate) fn start_streaming(
 	runtime.stream_service = service;
 	runtime.stream_video_encoder = video_encoder;
 	runtime.stream_audio_encoder = audio_encoder;
-	Ok("Streaming started".to_string())
+	Ok(())
 }
 
 pub(crate) fn stop_streaming(state: tauri::State<crate::ObsState>) -> Result<String, String> {
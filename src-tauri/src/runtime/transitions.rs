@@ -0,0 +1,475 @@
+// Program transitions: a libobs transition source sits on output channel 0
+// and scene switches go through obs_transition_start. Configuration lives in
+// `ui_profile.tools.transitions`, shared with the transitions editor.
+
+fn transition_type_available(id: &str) -> bool {
+	let Ok(id_c) = std::ffi::CString::new(id) else {
+		return false;
+	};
+	unsafe { !revo_lib::obs::obs_source_get_display_name(id_c.as_ptr()).is_null() }
+}
+
+fn kind_supported(config: &crate::settings::core::TransitionSettings) -> bool {
+	config
+		.kind
+		.is_some_and(|kind| transition_type_available(kind.obs_id()))
+}
+
+unsafe fn set_string(data: *mut revo_lib::obs::obs_data, key: &str, value: &str) {
+	let key_c = std::ffi::CString::new(key).unwrap();
+	let Ok(value_c) = std::ffi::CString::new(value) else {
+		return;
+	};
+	revo_lib::obs::obs_data_set_string(data, key_c.as_ptr(), value_c.as_ptr());
+}
+
+unsafe fn set_int(data: *mut revo_lib::obs::obs_data, key: &str, value: i64) {
+	let key_c = std::ffi::CString::new(key).unwrap();
+	revo_lib::obs::obs_data_set_int(data, key_c.as_ptr(), value);
+}
+
+unsafe fn set_bool(data: *mut revo_lib::obs::obs_data, key: &str, value: bool) {
+	let key_c = std::ffi::CString::new(key).unwrap();
+	revo_lib::obs::obs_data_set_bool(data, key_c.as_ptr(), value);
+}
+
+unsafe fn set_double(data: *mut revo_lib::obs::obs_data, key: &str, value: f64) {
+	let key_c = std::ffi::CString::new(key).unwrap();
+	revo_lib::obs::obs_data_set_double(data, key_c.as_ptr(), value);
+}
+
+fn param_percent(config: &crate::settings::core::TransitionSettings, key: &str, default: f64) -> f64 {
+	config
+		.param(key)
+		.and_then(|v| v.parse::<f64>().ok())
+		.filter(|v| v.is_finite())
+		.unwrap_or(default)
+		.clamp(0.0, 100.0)
+}
+
+// Resolved stinger settings and the media file the stinger plays.
+type StingerMedia = (crate::settings::core::StingerSettings, std::path::PathBuf);
+
+// Sequence stingers may run an ffmpeg render here, so callers resolve the
+// media before taking the runtime lock and hand it to `activate`.
+fn stinger_media(
+	config: &crate::settings::core::TransitionSettings,
+) -> Result<Option<StingerMedia>, String> {
+	if config.kind != Some(crate::settings::core::TransitionKind::Stinger) {
+		return Ok(None);
+	}
+	let stinger = crate::settings::core::stinger_settings(config)?;
+	let path = super::stinger::stinger_media(config, &stinger)?;
+	Ok(Some((stinger, path)))
+}
+
+/// The profile's selected transition with its stinger media resolved, ready
+/// to go on air once the runtime lock is held.
+pub(crate) struct PreparedTransition {
+	config: crate::settings::core::TransitionSettings,
+	stinger: Result<Option<StingerMedia>, String>,
+}
+
+pub(crate) fn prepare_selected_transition() -> PreparedTransition {
+	let config = crate::settings::core::settings_get()
+		.ok()
+		.map(|s| crate::settings::core::transitions_config(&s))
+		.unwrap_or_default();
+	let config = config
+		.active()
+		.cloned()
+		.unwrap_or_else(crate::settings::core::TransitionSettings::cut);
+	let stinger = stinger_media(&config);
+	PreparedTransition { config, stinger }
+}
+
+// Maps the editor's params onto the settings each libobs transition reads.
+unsafe fn transition_data(
+	config: &crate::settings::core::TransitionSettings,
+	stinger: Option<&StingerMedia>,
+) -> *mut revo_lib::obs::obs_data {
+	let data = revo_lib::obs::obs_data_create();
+	let direction = config.param("direction").unwrap_or("left");
+	match config.kind {
+		Some(crate::settings::core::TransitionKind::Swipe) => {
+			set_string(data, "direction", direction);
+			set_bool(data, "swipe_in", config.param("swipe_in") == Some("true"));
+		}
+		Some(crate::settings::core::TransitionKind::Slide) => {
+			set_string(data, "direction", direction);
+		}
+		Some(crate::settings::core::TransitionKind::FadeToColor) => {
+			let color = config
+				.param("color")
+				.and_then(crate::sources::helpers::parse_color_abgr)
+				.unwrap_or(0xFF00_0000);
+			set_int(data, "color", color as i64);
+			set_int(data, "switch_point", param_percent(config, "switch_point_pct", 50.0) as i64);
+		}
+		Some(crate::settings::core::TransitionKind::LumaWipe) => {
+			let (image, invert) = match direction {
+				"right" => ("linear-h.png", true),
+				"up" => ("linear-v.png", true),
+				"down" => ("linear-v.png", false),
+				_ => ("linear-h.png", false),
+			};
+			set_string(data, "luma_image", config.param("luma_image").unwrap_or(image));
+			set_bool(data, "luma_invert", invert);
+			set_double(data, "luma_softness", param_percent(config, "softness_pct", 35.0) / 100.0);
+		}
+		Some(crate::settings::core::TransitionKind::Stinger) => {
+			if let Some((stinger, path)) = stinger {
+				set_string(data, "path", &path.to_string_lossy());
+				// tp_type 0: transition point in milliseconds.
+				set_int(data, "tp_type", 0);
+				set_int(data, "transition_point", stinger.transition_point_ms as i64);
+				let fade_style = match stinger.audio_fade {
+					crate::settings::core::StingerAudioFade::FadeOutIn => 0,
+					crate::settings::core::StingerAudioFade::Crossfade => 1,
+				};
+				set_int(data, "audio_fade_style", fade_style);
+			}
+		}
+		_ => {}
+	}
+	data
+}
+
+fn create_transition_source(
+	config: &crate::settings::core::TransitionSettings,
+	stinger: Option<&StingerMedia>,
+) -> Result<(crate::settings::core::TransitionKind, *mut revo_lib::obs::obs_source), String> {
+	let kind = config
+		.kind
+		.ok_or_else(|| format!("transition kind '{}' is not supported", config.raw_kind))?;
+	if !transition_type_available(kind.obs_id()) {
+		return Err(format!("{} is unavailable (obs-transitions module not loaded)", kind.obs_id()));
+	}
+	if kind == crate::settings::core::TransitionKind::Stinger && stinger.is_none() {
+		return Err("stinger media was not resolved".to_string());
+	}
+	let id_c = std::ffi::CString::new(kind.obs_id()).unwrap();
+	let name_c = std::ffi::CString::new(format!("revo_transition_{}", config.id))
+		.map_err(|_| "transition id invalid".to_string())?;
+	let source = unsafe {
+		let data = transition_data(config, stinger);
+		let source = revo_lib::obs::obs_source_create_private(id_c.as_ptr(), name_c.as_ptr(), data);
+		revo_lib::obs::obs_data_release(data);
+		source
+	};
+	if source.is_null() {
+		return Err(format!("failed to create {}", kind.obs_id()));
+	}
+	Ok((kind, source))
+}
+
+fn current_scene_source(runtime: &crate::ObsRuntime) -> *mut revo_lib::obs::obs_source {
+	runtime
+		.current_scene
+		.as_ref()
+		.and_then(|name| runtime.scenes.get(name))
+		.map(|scene| scene.scene_source)
+		.unwrap_or(std::ptr::null_mut())
+}
+
+/// Puts `config` on the program output, carrying over the scene on air.
+fn activate(
+	runtime: &mut crate::ObsRuntime,
+	config: &crate::settings::core::TransitionSettings,
+	stinger: Option<&StingerMedia>,
+) -> Result<(), String> {
+	let (kind, source) = create_transition_source(config, stinger)?;
+	let scene_source = current_scene_source(runtime);
+	unsafe {
+		if !scene_source.is_null() {
+			revo_lib::obs::obs_transition_set(source, scene_source);
+		}
+		revo_lib::obs::obs_set_output_source(0, source);
+	}
+	release_program_transition(runtime);
+	runtime.program_transition = Some(crate::TransitionState {
+		id: config.id.clone(),
+		kind,
+		source,
+		duration_ms: config.duration_ms,
+	});
+	Ok(())
+}
+
+/// Called once scenes exist; a broken profile entry falls back to a cut.
+pub(crate) fn ensure_program_transition(runtime: &mut crate::ObsRuntime, prepared: PreparedTransition) {
+	if runtime.program_transition.is_none() {
+		apply_selected_transition(runtime, prepared);
+	}
+}
+
+pub(crate) fn apply_selected_transition(runtime: &mut crate::ObsRuntime, prepared: PreparedTransition) {
+	let PreparedTransition { config, stinger } = prepared;
+	let result = stinger.and_then(|stinger| activate(runtime, &config, stinger.as_ref()));
+	if let Err(err) = result {
+		crate::logging::debug::push_debug_log_entry(
+			"transition_activate_failed".to_string(),
+			Some(serde_json::json!({ "id": config.id, "error": err })),
+		);
+		let _ = activate(runtime, &crate::settings::core::TransitionSettings::cut(), None);
+	}
+}
+
+pub(crate) fn release_program_transition(runtime: &mut crate::ObsRuntime) {
+	if let Some(previous) = runtime.program_transition.take() {
+		unsafe {
+			revo_lib::obs::obs_transition_clear(previous.source);
+			revo_lib::obs::obs_source_release(previous.source);
+		}
+	}
+}
+
+/// Shows `scene_source` on the program output. With `animate` the selected
+/// transition runs; otherwise (or for cuts) the scene is swapped in directly.
+pub(crate) fn show_scene(
+	runtime: &crate::ObsRuntime,
+	scene_source: *mut revo_lib::obs::obs_source,
+	animate: bool,
+) {
+	let Some(transition) = runtime.program_transition.as_ref() else {
+		unsafe { revo_lib::obs::obs_set_output_source(0, scene_source) };
+		return;
+	};
+	unsafe {
+		let animated = animate
+			&& transition.kind != crate::settings::core::TransitionKind::Cut
+			&& transition.duration_ms > 0;
+		if animated
+			&& revo_lib::obs::obs_transition_start(
+				transition.source,
+				revo_lib::obs::obs_transition_mode_OBS_TRANSITION_MODE_AUTO,
+				transition.duration_ms,
+				scene_source,
+			) {
+			return;
+		}
+		// Also lands here when a transition is already running.
+		revo_lib::obs::obs_transition_force_stop(transition.source);
+		revo_lib::obs::obs_transition_set(transition.source, scene_source);
+	}
+}
+
+fn transition_info(
+	runtime: &crate::ObsRuntime,
+	config: &crate::settings::core::TransitionSettings,
+	selected: bool,
+) -> crate::TransitionInfo {
+	crate::TransitionInfo {
+		id: config.id.clone(),
+		name: config.name.clone(),
+		kind: config.raw_kind.clone(),
+		duration_ms: config.duration_ms,
+		params: config.params.clone(),
+		selected,
+		supported: kind_supported(config),
+		live: runtime
+			.program_transition
+			.as_ref()
+			.is_some_and(|t| t.id == config.id),
+	}
+}
+
+pub(crate) fn list_transitions(
+	state: tauri::State<crate::ObsState>,
+) -> Result<Vec<crate::TransitionInfo>, String> {
+	let settings = crate::settings::core::settings_get()?;
+	let config = crate::settings::core::transitions_config(&settings);
+	let selected_id = config.active().map(|t| t.id.clone());
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	Ok(config
+		.items
+		.iter()
+		.map(|t| transition_info(&runtime, t, selected_id.as_deref() == Some(t.id.as_str())))
+		.collect())
+}
+
+fn transitions_section_mut(
+	settings: &mut crate::AppSettings,
+) -> &mut serde_json::Map<String, serde_json::Value> {
+	crate::settings::core::ui_profile_section_mut(settings, &["tools", "transitions"])
+}
+
+/// Creates or updates a transition entry in the profile. When it is the one
+/// on air, the program transition is rebuilt with the new settings.
+pub(crate) fn configure_transition(
+	state: tauri::State<crate::ObsState>,
+	update: crate::TransitionUpdate,
+) -> Result<crate::TransitionInfo, String> {
+	let kind = update.kind.as_deref().map(str::trim).filter(|k| !k.is_empty());
+	if let Some(kind) = kind {
+		if crate::settings::core::TransitionKind::parse(kind).is_none() {
+			return Err(format!("unsupported transition kind: {kind}"));
+		}
+	}
+	if let Some(raw) = update.params.get("duration_ms").map(|v| v.trim()).filter(|v| !v.is_empty()) {
+		let valid = raw
+			.parse::<u32>()
+			.is_ok_and(|ms| ms <= crate::settings::core::MAX_TRANSITION_DURATION_MS);
+		if !valid {
+			return Err(format!(
+				"transition duration must be 0-{} ms",
+				crate::settings::core::MAX_TRANSITION_DURATION_MS
+			));
+		}
+	}
+
+	let mut settings = crate::settings::core::settings_get()?;
+	let section = transitions_section_mut(&mut settings);
+	let items = section
+		.entry("items")
+		.or_insert_with(|| serde_json::Value::Array(Vec::new()));
+	if !items.is_array() {
+		*items = serde_json::Value::Array(Vec::new());
+	}
+	let items = items.as_array_mut().unwrap();
+
+	let id = update
+		.id
+		.as_deref()
+		.map(str::trim)
+		.filter(|id| !id.is_empty())
+		.map(str::to_string);
+	let existing = id.as_deref().and_then(|id| {
+		items
+			.iter()
+			.position(|row| row.get("id").and_then(|v| v.as_str()) == Some(id))
+	});
+	let idx = match existing {
+		Some(idx) => idx,
+		None => {
+			let kind = kind.unwrap_or("fade");
+			let name = format!("Transition {}", items.len() + 1);
+			items.push(serde_json::json!({
+				"id": id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+				"name": name,
+				"kind": kind,
+				"params": {},
+			}));
+			items.len() - 1
+		}
+	};
+
+	let row = items[idx]
+		.as_object_mut()
+		.ok_or_else(|| "invalid transition entry".to_string())?;
+	if let Some(name) = update.name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+		row.insert("name".to_string(), serde_json::Value::String(name.to_string()));
+	}
+	if let Some(kind) = kind {
+		row.insert("kind".to_string(), serde_json::Value::String(kind.to_lowercase()));
+	}
+	let params = row
+		.entry("params")
+		.or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+	if !params.is_object() {
+		*params = serde_json::Value::Object(serde_json::Map::new());
+	}
+	let params = params.as_object_mut().unwrap();
+	for (key, value) in &update.params {
+		if value.trim().is_empty() {
+			params.remove(key);
+		} else {
+			params.insert(key.clone(), serde_json::Value::String(value.trim().to_string()));
+		}
+	}
+	let config = crate::settings::core::transition_from_value(&items[idx])
+		.ok_or_else(|| "invalid transition entry".to_string())?;
+	let selected_id = crate::settings::core::transitions_config(&settings)
+		.active()
+		.map(|t| t.id.clone());
+	crate::settings::core::settings_save(settings)?;
+	crate::settings::core::notify_settings_changed();
+	// Only the entry on air needs its media; a broken stinger is still saved.
+	let stinger = stinger_media(&config);
+
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	let live = runtime
+		.program_transition
+		.as_ref()
+		.is_some_and(|t| t.id == config.id);
+	if runtime.initialized && live {
+		activate(&mut runtime, &config, stinger?.as_ref())?;
+	}
+	Ok(transition_info(
+		&runtime,
+		&config,
+		selected_id.as_deref() == Some(config.id.as_str()),
+	))
+}
+
+/// Makes `id` the default transition and puts it on the program output.
+pub(crate) fn select_transition(
+	state: tauri::State<crate::ObsState>,
+	id: String,
+) -> Result<crate::TransitionInfo, String> {
+	let id = id.trim().to_string();
+	let mut settings = crate::settings::core::settings_get()?;
+	let config = crate::settings::core::transitions_config(&settings)
+		.items
+		.into_iter()
+		.find(|t| t.id == id)
+		.ok_or_else(|| format!("transition not found: {id}"))?;
+	if config.kind.is_none() {
+		return Err(format!("transition kind '{}' is not supported", config.raw_kind));
+	}
+	let stinger = stinger_media(&config)?;
+
+	let mut runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if runtime.initialized {
+		activate(&mut runtime, &config, stinger.as_ref())?;
+	}
+	transitions_section_mut(&mut settings)
+		.insert("activeTransitionId".to_string(), serde_json::Value::String(id));
+	crate::settings::core::settings_save(settings)?;
+	crate::settings::core::notify_settings_changed();
+	Ok(transition_info(&runtime, &config, true))
+}
+
+/// `tools.transitions` of the active profile, saved alongside exported scenes.
+pub(crate) fn collection_transitions() -> serde_json::Value {
+	crate::settings::core::settings_get()
+		.ok()
+		.and_then(|s| {
+			crate::settings::core::ui_profile_section(&s, &["tools", "transitions"])
+				.map(|section| serde_json::Value::Object(section.clone()))
+		})
+		.unwrap_or(serde_json::Value::Null)
+}
+
+/// Restores transitions from an imported scene collection and returns the
+/// selected one for `apply_selected_transition`. Collections without a
+/// `transitions.items` list leave the profile as is.
+pub(crate) fn import_collection_transitions(
+	value: &serde_json::Value,
+) -> Result<Option<PreparedTransition>, String> {
+	let Some(items) = value.get("items").and_then(|v| v.as_array()) else {
+		return Ok(None);
+	};
+	let mut settings = crate::settings::core::settings_get()?;
+	let section = transitions_section_mut(&mut settings);
+	section.insert("items".to_string(), serde_json::Value::Array(items.clone()));
+	match value.get("activeTransitionId").and_then(|v| v.as_str()) {
+		Some(id) => {
+			section.insert("activeTransitionId".to_string(), serde_json::Value::String(id.to_string()));
+		}
+		None => {
+			section.remove("activeTransitionId");
+		}
+	}
+	crate::settings::core::settings_save(settings)?;
+	Ok(Some(prepare_selected_transition()))
+}
@@ -293,7 +293,8 @@ pub(crate) fn export_scene_collection(
 
 	let doc = serde_json::json!({
 		"name": "RevoStream",
-		"scenes": scenes_json
+		"scenes": scenes_json,
+		"transitions": crate::runtime::transitions::collection_transitions()
 	});
 	serde_json::to_string_pretty(&doc).map_err(|e| format!("export failed: {e}"))
 }
@@ -671,6 +672,29 @@ pub(crate) fn import_scene_collection(
 		let _ = crate::set_current_scene_internal(&mut runtime, first.as_str());
 	}
 
+	// RevoStream collections carry `{ items, activeTransitionId }`; OBS ones use
+	// a plain array, which is not imported. The lock is released meanwhile since
+	// a sequence stinger may need an ffmpeg render.
+	if let Some(transitions) = doc.get("transitions").filter(|v| v.is_object()) {
+		drop(runtime);
+		match crate::runtime::transitions::import_collection_transitions(transitions) {
+			Ok(Some(prepared)) => {
+				let mut runtime = state
+					.runtime
+					.lock()
+					.map_err(|_| "state poisoned".to_string())?;
+				if runtime.initialized {
+					crate::runtime::transitions::apply_selected_transition(&mut runtime, prepared);
+				}
+			}
+			Ok(None) => {}
+			Err(err) => crate::logging::debug::push_debug_log_entry(
+				"transition_import_failed".to_string(),
+				Some(serde_json::json!({ "error": err })),
+			),
+		}
+	}
+
 	if !created_any {
 		return Err("no sources imported (unsupported types?)".to_string());
 	}
@@ -49,7 +49,12 @@ pub(crate) fn set_current_scene(
 	if trimmed.is_empty() {
 		return Err("scene name required".to_string());
 	}
-	crate::set_current_scene_internal(&mut runtime, trimmed)?;
+	let changed = runtime.current_scene.as_deref() != Some(trimmed);
+	crate::scenes::scene_state::transition_to_scene(&mut runtime, trimmed)?;
+	if changed {
+		crate::runtime::recording_history::note_scene(trimmed);
+		crate::runtime::record_chapters::scene_switched(&runtime, trimmed);
+	}
 	Ok(format!("active scene: {trimmed}"))
 }
 
@@ -27,11 +27,21 @@ pub(crate) fn set_current_scene_internal(
 	runtime: &mut crate::ObsRuntime,
 	name: &str,
 ) -> Result<(), String> {
-	let next = runtime
+	switch_scene(runtime, name, false)
+}
+
+/// Like `set_current_scene_internal`, but runs the selected program transition.
+pub(crate) fn transition_to_scene(runtime: &mut crate::ObsRuntime, name: &str) -> Result<(), String> {
+	switch_scene(runtime, name, true)
+}
+
+fn switch_scene(runtime: &mut crate::ObsRuntime, name: &str, animate: bool) -> Result<(), String> {
+	let next_source = runtime
 		.scenes
 		.get(name)
-		.ok_or_else(|| "scene not found".to_string())?;
-	if next.scene_source.is_null() {
+		.ok_or_else(|| "scene not found".to_string())?
+		.scene_source;
+	if next_source.is_null() {
 		return Err("scene source unavailable".to_string());
 	}
 
@@ -49,11 +59,11 @@ pub(crate) fn set_current_scene_internal(
 		}
 	}
 
+	crate::runtime::transitions::show_scene(runtime, next_source, animate);
 	unsafe {
-		revo_lib::obs::obs_set_output_source(0, next.scene_source);
-		revo_lib::obs::obs_source_inc_showing(next.scene_source);
+		revo_lib::obs::obs_source_inc_showing(next_source);
 		if !runtime.preview_view.is_null() {
-			revo_lib::obs::obs_view_set_source(runtime.preview_view, 0, next.scene_source);
+			revo_lib::obs::obs_view_set_source(runtime.preview_view, 0, next_source);
 		}
 	}
 	runtime.current_scene = Some(name.to_string());
@@ -1 +1,84 @@
 // Audio settings bridge module.
+
+fn read_audio_info() -> Option<crate::AudioInfo> {
+	unsafe {
+		let mut oai: revo_lib::obs::obs_audio_info = std::mem::zeroed();
+		if !revo_lib::obs::obs_get_audio_info(&mut oai as *mut _) {
+			return None;
+		}
+		let layout = crate::runtime::helpers::speaker_layout_from_obs(oai.speakers);
+		Some(crate::AudioInfo {
+			sample_rate: oai.samples_per_sec,
+			speakers: layout
+				.map(|l| l.as_str().to_string())
+				.unwrap_or_else(|| oai.speakers.to_string()),
+			channels: layout.map(|l| l.channels()).unwrap_or(0),
+		})
+	}
+}
+
+pub(crate) fn get_audio_info(state: tauri::State<crate::ObsState>) -> Result<crate::AudioInfo, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	read_audio_info().ok_or_else(|| "obs_get_audio_info failed".to_string())
+}
+
+/// Resets the audio engine to the profile's sample rate and speaker layout.
+/// When libobs refuses the reset the values only take effect on the next
+/// start, reported as `restart_required`.
+pub(crate) fn apply_audio_settings(
+	state: tauri::State<crate::ObsState>,
+) -> Result<crate::AudioResetStatus, String> {
+	let settings = crate::settings::core::settings_get()?;
+	let audio = crate::settings::core::audio_engine_settings(&settings)?;
+
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	if crate::settings::video_settings::any_output_active(&runtime) {
+		return Err("Cannot change audio while recording/streaming".to_string());
+	}
+
+	let requested = format!("{} Hz {}", audio.sample_rate, audio.speakers.as_str());
+	let current = read_audio_info().ok_or_else(|| "obs_get_audio_info failed".to_string())?;
+	if current.sample_rate == audio.sample_rate && current.speakers == audio.speakers.as_str() {
+		return Ok(crate::AudioResetStatus {
+			info: current,
+			applied: true,
+			restart_required: false,
+			message: format!("Audio already running at {requested}"),
+		});
+	}
+
+	let applied = crate::runtime::helpers::reset_audio(&audio);
+	if !applied {
+		crate::logging::debug::push_debug_log_entry(
+			"audio_reset_failed".to_string(),
+			Some(serde_json::json!({
+				"sample_rate": audio.sample_rate,
+				"speakers": audio.speakers.as_str(),
+			})),
+		);
+	}
+	let info = read_audio_info().ok_or_else(|| "obs_get_audio_info failed".to_string())?;
+	let message = if applied {
+		format!("Audio reset to {requested}")
+	} else {
+		format!("Audio will switch to {requested} after a restart")
+	};
+	Ok(crate::AudioResetStatus {
+		info,
+		applied,
+		restart_required: !applied,
+		message,
+	})
+}
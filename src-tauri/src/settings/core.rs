@@ -163,6 +163,20 @@ pub(crate) fn normalize_settings(settings: &mut crate::AppSettings) {
 		Some(v) if !v.is_empty() => Some(v),
 		_ => default_whip_url(),
 	};
+	normalize_optional_trimmed(&mut settings.whip_bearer_token);
+
+	if let Some(targets) = settings.stream_targets.as_mut() {
+		for target in targets.iter_mut() {
+			target.name = target.name.trim().to_string();
+			target.url = target.url.trim().to_string();
+			target.key = target.key.trim().to_string();
+			normalize_optional_trimmed(&mut target.service);
+			normalize_optional_trimmed(&mut target.server);
+		}
+		targets.retain(|t| !t.name.is_empty() && (!t.url.is_empty() || t.service.is_some()));
+		let mut seen = std::collections::HashSet::new();
+		targets.retain(|t| seen.insert(t.name.clone()));
+	}
 
 	settings.auto_retry_preview = Some(settings.auto_retry_preview.unwrap_or(true));
 	settings.autorescale_inputs = Some(settings.autorescale_inputs.unwrap_or(false));
@@ -227,6 +241,16 @@ pub(crate) fn parse_settings_raw(raw: &str) -> crate::AppSettings {
 		if let Some(v) = obj.get("whip_url").and_then(|v| v.as_str()) {
 			settings.whip_url = Some(v.to_string());
 		}
+		if let Some(v) = obj.get("whip_bearer_token").and_then(|v| v.as_str()) {
+			settings.whip_bearer_token = Some(v.to_string());
+		}
+		if let Some(v) = obj.get("stream_targets").and_then(|v| v.as_array()) {
+			settings.stream_targets = Some(
+				v.iter()
+					.filter_map(|item| serde_json::from_value::<crate::StreamTargetConfig>(item.clone()).ok())
+					.collect(),
+			);
+		}
 		if let Some(v) = obj.get("auto_retry_preview").and_then(|v| v.as_bool()) {
 			settings.auto_retry_preview = Some(v);
 		}
@@ -256,6 +280,8 @@ pub(crate) fn default_settings() -> crate::AppSettings {
 		scene_resolution: default_scene_resolution(),
 		whep_url: default_whep_url(),
 		whip_url: default_whip_url(),
+		whip_bearer_token: None,
+		stream_targets: None,
 		auto_retry_preview: Some(true),
 		autorescale_inputs: Some(false),
 		ui_profile: None,
@@ -366,8 +392,26 @@ pub(crate) fn settings_get() -> Result<crate::AppSettings, String> {
 	Ok(settings)
 }
 
+// The settings modal only sends the fields it edits; values managed purely by the
+// backend are carried over from the stored profile when the payload omits them.
+// An empty string is an explicit clear: it is kept here and normalized to None.
+pub(crate) fn preserve_backend_only_fields(settings: &mut crate::AppSettings) {
+	if settings.whip_bearer_token.is_some() && settings.stream_targets.is_some() {
+		return;
+	}
+	if let Ok(stored) = settings_get() {
+		if settings.whip_bearer_token.is_none() {
+			settings.whip_bearer_token = stored.whip_bearer_token;
+		}
+		if settings.stream_targets.is_none() {
+			settings.stream_targets = stored.stream_targets;
+		}
+	}
+}
+
 pub(crate) fn settings_save(settings: crate::AppSettings) -> Result<String, String> {
 	let mut normalized_settings = settings;
+	preserve_backend_only_fields(&mut normalized_settings);
 	normalize_settings(&mut normalized_settings);
 
 	let root = resolve_root_dir(normalized_settings.root_dir.clone())?;
@@ -383,6 +427,17 @@ pub(crate) fn settings_save(settings: crate::AppSettings) -> Result<String, Stri
 	Ok(format!("Profile saved to {}", path.to_string_lossy()))
 }
 
+/// Tells the frontend the backend rewrote the profile, so drafts built from
+/// the old one are reloaded before the next save.
+pub(crate) const SETTINGS_CHANGED_EVENT: &str = "obs-settings-changed";
+
+pub(crate) fn notify_settings_changed() {
+	if let Some(handle) = crate::runtime::output_state::app_handle() {
+		use tauri::Emitter;
+		let _ = handle.emit(SETTINGS_CHANGED_EVENT, ());
+	}
+}
+
 pub(crate) fn runtime_data_dir() -> Result<std::path::PathBuf, String> {
 	let cwd = crate::utils::fs::startup_cwd()?;
 	if cwd.file_name().and_then(|s| s.to_str()) == Some("src-tauri") {
@@ -578,3 +633,1540 @@ pub(crate) fn resolve_root_dir(root_dir: Option<String>) -> Result<std::path::Pa
 
 	Err("Unable to resolve RevoStream root directory containing libobs effects".to_string())
 }
+
+pub(crate) fn ui_profile_section<'a>(
+	settings: &'a crate::AppSettings,
+	path: &[&str],
+) -> Option<&'a serde_json::Map<String, serde_json::Value>> {
+	let mut current = settings.ui_profile.as_ref()?;
+	for key in path {
+		current = current.get(*key)?;
+	}
+	current.as_object()
+}
+
+/// Mutable counterpart of `ui_profile_section`; missing or non-object levels
+/// are replaced with empty objects.
+pub(crate) fn ui_profile_section_mut<'a>(
+	settings: &'a mut crate::AppSettings,
+	path: &[&str],
+) -> &'a mut serde_json::Map<String, serde_json::Value> {
+	let mut current = settings
+		.ui_profile
+		.get_or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+	for key in path {
+		if !current.is_object() {
+			*current = serde_json::Value::Object(serde_json::Map::new());
+		}
+		current = current
+			.as_object_mut()
+			.unwrap()
+			.entry(*key)
+			.or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+	}
+	if !current.is_object() {
+		*current = serde_json::Value::Object(serde_json::Map::new());
+	}
+	current.as_object_mut().unwrap()
+}
+
+pub(crate) fn profile_field(
+	section: Option<&serde_json::Map<String, serde_json::Value>>,
+	key: &str,
+) -> Option<String> {
+	let value = section?.get(key)?;
+	let raw = match value {
+		serde_json::Value::String(s) => s.trim().to_string(),
+		serde_json::Value::Number(n) => n.to_string(),
+		serde_json::Value::Bool(b) => b.to_string(),
+		_ => return None,
+	};
+	if raw.is_empty() {
+		None
+	} else {
+		Some(raw)
+	}
+}
+
+pub(crate) fn profile_bool(
+	section: Option<&serde_json::Map<String, serde_json::Value>>,
+	key: &str,
+) -> Option<bool> {
+	match section?.get(key)? {
+		serde_json::Value::Bool(b) => Some(*b),
+		serde_json::Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+			"true" | "1" | "yes" | "on" => Some(true),
+			"false" | "0" | "no" | "off" => Some(false),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Video encoder parameters taken from `ui_profile.output.streaming`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StreamingEncoderSettings {
+	/// `streamingVideoEncoder`; None leaves the choice to the encoder preference.
+	pub(crate) video_encoder: Option<String>,
+	pub(crate) bitrate: i64,
+	pub(crate) rate_control: String,
+	pub(crate) keyint_sec: i64,
+	pub(crate) cpu_usage_preset: Option<String>,
+	pub(crate) profile: Option<String>,
+	pub(crate) tune: Option<String>,
+	pub(crate) extra_options: Vec<(String, String)>,
+	// Low-latency B-frame-less defaults used when the profile has no streaming section.
+	pub(crate) low_latency: bool,
+}
+
+impl Default for StreamingEncoderSettings {
+	fn default() -> Self {
+		Self {
+			video_encoder: None,
+			bitrate: 2500,
+			rate_control: "CBR".to_string(),
+			keyint_sec: 2,
+			cpu_usage_preset: None,
+			profile: None,
+			tune: Some("zerolatency".to_string()),
+			extra_options: Vec::new(),
+			low_latency: true,
+		}
+	}
+}
+
+pub(crate) const X264_PRESETS: [&str; 10] = [
+	"ultrafast", "superfast", "veryfast", "faster", "fast", "medium", "slow", "slower", "veryslow",
+	"placebo",
+];
+pub(crate) const X264_TUNES: [&str; 8] = [
+	"film", "animation", "grain", "stillimage", "psnr", "ssim", "fastdecode", "zerolatency",
+];
+
+pub(crate) fn parse_encoder_extra_options(raw: &str) -> Result<Vec<(String, String)>, String> {
+	let mut out = Vec::new();
+	for token in raw.split(|c: char| c.is_whitespace() || c == ':').filter(|t| !t.is_empty()) {
+		let Some((key, value)) = token.split_once('=') else {
+			return Err(format!("invalid encoder option '{token}' (expected key=value)"));
+		};
+		let key = key.trim();
+		if key.is_empty() {
+			return Err(format!("invalid encoder option '{token}' (empty key)"));
+		}
+		out.push((key.to_string(), value.trim().to_string()));
+	}
+	Ok(out)
+}
+
+pub(crate) fn streaming_encoder_settings(
+	settings: &crate::AppSettings,
+) -> Result<StreamingEncoderSettings, String> {
+	let Some(section) = ui_profile_section(settings, &["output", "streaming"]) else {
+		return Ok(StreamingEncoderSettings::default());
+	};
+	let section = Some(section);
+	let advanced = profile_bool(section, "streamingAdvanced").unwrap_or(false);
+	let video_encoder = profile_field(section, "streamingVideoEncoder");
+	// Rate control and profile are checked against the chosen encoder's own
+	// properties when it is created; only x264's preset/tune lists are fixed.
+	let x264 = video_encoder
+		.as_deref()
+		.map_or(true, |id| matches!(id.to_lowercase().as_str(), "x264" | "obs_x264"));
+	let mut errors: Vec<String> = Vec::new();
+
+	let bitrate = match profile_field(section, "streamingBitrate") {
+		None => 2500,
+		Some(raw) => match raw.parse::<i64>() {
+			Ok(v) if (1..=500_000).contains(&v) => v,
+			_ => {
+				errors.push(format!("streamingBitrate '{raw}' is not a bitrate in kbps"));
+				2500
+			}
+		},
+	};
+
+	let keyint_sec = match profile_field(section, "streamingKeyframeInterval") {
+		None => 2,
+		Some(raw) => match raw.parse::<i64>() {
+			Ok(v) if (0..=20).contains(&v) => v,
+			_ => {
+				errors.push(format!(
+					"streamingKeyframeInterval '{raw}' must be 0-20 seconds (0 = auto)"
+				));
+				2
+			}
+		},
+	};
+
+	let profile = profile_field(section, "streamingProfile")
+		.map(|v| v.to_lowercase())
+		.filter(|v| v != "none");
+
+	let mut rate_control = "CBR".to_string();
+	let mut cpu_usage_preset = None;
+	let mut tune = None;
+	let mut extra_options = Vec::new();
+	if advanced {
+		if let Some(raw) = profile_field(section, "streamingRateControl") {
+			rate_control = raw.to_uppercase();
+		}
+		match profile_field(section, "streamingCpuUsagePreset").map(|v| v.to_lowercase()) {
+			None => {}
+			Some(v) if !x264 || X264_PRESETS.contains(&v.as_str()) => cpu_usage_preset = Some(v),
+			Some(v) => errors.push(format!("streamingCpuUsagePreset '{v}' is not an x264 preset")),
+		}
+		match profile_field(section, "streamingTune").map(|v| v.to_lowercase()) {
+			None => {}
+			Some(v) if v == "none" => {}
+			Some(v) if !x264 || X264_TUNES.contains(&v.as_str()) => tune = Some(v),
+			Some(v) => errors.push(format!("streamingTune '{v}' is not an x264 tune")),
+		}
+		if let Some(raw) = profile_field(section, "streamingExtraOptions") {
+			match parse_encoder_extra_options(&raw) {
+				Ok(parsed) => extra_options = parsed,
+				Err(err) => errors.push(format!("streamingExtraOptions: {err}")),
+			}
+		}
+	}
+
+	// These are handed to libobs as C strings.
+	for (key, value) in [
+		("streamingVideoEncoder", video_encoder.as_deref()),
+		("streamingRateControl", Some(rate_control.as_str())),
+		("streamingProfile", profile.as_deref()),
+		("streamingCpuUsagePreset", cpu_usage_preset.as_deref()),
+		("streamingTune", tune.as_deref()),
+	] {
+		if value.is_some_and(|v| v.contains('\0')) {
+			errors.push(format!("{key} must not contain NUL characters"));
+		}
+	}
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid streaming encoder settings: {}", errors.join("; ")));
+	}
+
+	Ok(StreamingEncoderSettings {
+		video_encoder,
+		bitrate,
+		rate_control,
+		keyint_sec,
+		cpu_usage_preset,
+		profile,
+		tune,
+		extra_options,
+		low_latency: false,
+	})
+}
+
+/// Whether the primary stream pushes to `whip_url` instead of `stream_url`
+/// (`ui_profile.output.streaming.streamingMode == "whip"`).
+pub(crate) fn whip_streaming_selected(settings: &crate::AppSettings) -> bool {
+	profile_field(ui_profile_section(settings, &["output", "streaming"]), "streamingMode")
+		.is_some_and(|mode| mode.eq_ignore_ascii_case("whip"))
+}
+
+/// Automatic reconnect policy from `ui_profile.output.streaming.streamingReconnect*`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct StreamReconnectSettings {
+	/// 0 disables automatic reconnects.
+	pub(crate) retries: i32,
+	pub(crate) delay_sec: i32,
+}
+
+impl Default for StreamReconnectSettings {
+	fn default() -> Self {
+		Self {
+			retries: 20,
+			delay_sec: 2,
+		}
+	}
+}
+
+pub(crate) fn stream_reconnect_settings(
+	settings: &crate::AppSettings,
+) -> Result<StreamReconnectSettings, String> {
+	let section = ui_profile_section(settings, &["output", "streaming"]);
+	let mut out = StreamReconnectSettings::default();
+	let mut errors: Vec<String> = Vec::new();
+
+	if let Some(raw) = profile_field(section, "streamingReconnectRetries") {
+		match raw.parse::<i32>() {
+			Ok(v) if (0..=10_000).contains(&v) => out.retries = v,
+			_ => errors.push(format!(
+				"streamingReconnectRetries '{raw}' must be 0-10000 (0 = off)"
+			)),
+		}
+	}
+	if let Some(raw) = profile_field(section, "streamingReconnectDelaySec") {
+		match raw.parse::<i32>() {
+			Ok(v) if (1..=30).contains(&v) => out.delay_sec = v,
+			_ => errors.push(format!("streamingReconnectDelaySec '{raw}' must be 1-30 seconds")),
+		}
+	}
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid stream reconnect settings: {}", errors.join("; ")));
+	}
+	Ok(out)
+}
+
+// One per libobs mixer (MAX_AUDIO_MIXES).
+pub(crate) const AUDIO_TRACK_COUNT: usize = 6;
+pub(crate) const AUDIO_TRACK_DEFAULT_BITRATE: i64 = 128;
+
+/// Per-track encoder settings from `ui_profile.output.audioTracks`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AudioTrackSettings {
+	/// 1-based track number; mixer index is `index - 1`.
+	pub(crate) index: usize,
+	pub(crate) bitrate: i64,
+	pub(crate) name: String,
+}
+
+pub(crate) fn audio_track_settings(
+	settings: &crate::AppSettings,
+) -> Result<Vec<AudioTrackSettings>, String> {
+	let section = ui_profile_section(settings, &["output", "audioTracks"]);
+	let mut errors: Vec<String> = Vec::new();
+	let tracks = (1..=AUDIO_TRACK_COUNT)
+		.map(|index| {
+			let bitrate_key = format!("audioChannel{index}Bitrate");
+			let bitrate = match profile_field(section, &bitrate_key) {
+				None => AUDIO_TRACK_DEFAULT_BITRATE,
+				Some(raw) => match raw.parse::<i64>() {
+					Ok(v) if (32..=1024).contains(&v) => v,
+					_ => {
+						errors.push(format!("{bitrate_key} '{raw}' must be 32-1024 kbps"));
+						AUDIO_TRACK_DEFAULT_BITRATE
+					}
+				},
+			};
+			let name = profile_field(section, &format!("audioChannel{index}Name"))
+				.unwrap_or_else(|| format!("Track {index}"));
+			AudioTrackSettings { index, bitrate, name }
+		})
+		.collect();
+	if !errors.is_empty() {
+		return Err(format!("Invalid audio track settings: {}", errors.join("; ")));
+	}
+	Ok(tracks)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum AudioSpeakerLayout {
+	Mono,
+	Stereo,
+	TwoPointOne,
+	Quad,
+	FourPointOne,
+	FivePointOne,
+	SevenPointOne,
+}
+
+impl AudioSpeakerLayout {
+	pub(crate) const ALL: [Self; 7] = [
+		Self::Mono,
+		Self::Stereo,
+		Self::TwoPointOne,
+		Self::Quad,
+		Self::FourPointOne,
+		Self::FivePointOne,
+		Self::SevenPointOne,
+	];
+
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::Mono => "mono",
+			Self::Stereo => "stereo",
+			Self::TwoPointOne => "2.1",
+			Self::Quad => "4.0",
+			Self::FourPointOne => "4.1",
+			Self::FivePointOne => "5.1",
+			Self::SevenPointOne => "7.1",
+		}
+	}
+
+	pub(crate) fn channels(self) -> u32 {
+		match self {
+			Self::Mono => 1,
+			Self::Stereo => 2,
+			Self::TwoPointOne => 3,
+			Self::Quad => 4,
+			Self::FourPointOne => 5,
+			Self::FivePointOne => 6,
+			Self::SevenPointOne => 8,
+		}
+	}
+
+	fn parse(raw: &str) -> Option<Self> {
+		let raw = raw.trim().to_lowercase();
+		let raw = match raw.as_str() {
+			"1" | "1.0" => "mono",
+			"2" | "2.0" => "stereo",
+			"quad" | "4" => "4.0",
+			other => other,
+		};
+		Self::ALL.into_iter().find(|l| l.as_str() == raw)
+	}
+}
+
+pub(crate) const AUDIO_SAMPLE_RATES: [u32; 2] = [44_100, 48_000];
+
+/// `ui_profile.audio.audioSampleRate` / `audioChannels`; applied by `obs_reset_audio`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AudioEngineSettings {
+	pub(crate) sample_rate: u32,
+	pub(crate) speakers: AudioSpeakerLayout,
+}
+
+impl Default for AudioEngineSettings {
+	fn default() -> Self {
+		Self {
+			sample_rate: 48_000,
+			speakers: AudioSpeakerLayout::Stereo,
+		}
+	}
+}
+
+pub(crate) fn audio_engine_settings(
+	settings: &crate::AppSettings,
+) -> Result<AudioEngineSettings, String> {
+	let section = ui_profile_section(settings, &["audio"]);
+	let mut out = AudioEngineSettings::default();
+	let mut errors: Vec<String> = Vec::new();
+
+	if let Some(raw) = profile_field(section, "audioSampleRate") {
+		match raw.parse::<u32>() {
+			Ok(rate) if AUDIO_SAMPLE_RATES.contains(&rate) => out.sample_rate = rate,
+			_ => errors.push(format!("audioSampleRate '{raw}' must be 44100 or 48000")),
+		}
+	}
+	if let Some(raw) = profile_field(section, "audioChannels") {
+		match AudioSpeakerLayout::parse(&raw) {
+			Some(speakers) => out.speakers = speakers,
+			None => errors.push(format!(
+				"audioChannels '{raw}' must be mono, stereo, 2.1, 4.0, 4.1, 5.1 or 7.1"
+			)),
+		}
+	}
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid audio settings: {}", errors.join("; ")));
+	}
+	Ok(out)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RecordingSplitMode {
+	Time,
+	Size,
+	Manual,
+}
+
+/// `ui_profile.output.recording.recordingSplitFile*`; time is in minutes, size in MB.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RecordingSplitSettings {
+	pub(crate) mode: RecordingSplitMode,
+	pub(crate) max_time_sec: i64,
+	pub(crate) max_size_mb: i64,
+}
+
+pub(crate) fn recording_split_settings(
+	settings: &crate::AppSettings,
+) -> Result<Option<RecordingSplitSettings>, String> {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	if !profile_bool(section, "recordingSplitFile").unwrap_or(false) {
+		return Ok(None);
+	}
+	let mode = match profile_field(section, "recordingSplitFileType")
+		.map(|v| v.to_lowercase())
+		.as_deref()
+	{
+		None | Some("time") => RecordingSplitMode::Time,
+		Some("size") => RecordingSplitMode::Size,
+		Some("manual") => RecordingSplitMode::Manual,
+		Some(other) => {
+			return Err(format!(
+				"recordingSplitFileType '{other}' must be time, size or manual"
+			))
+		}
+	};
+	let minutes = match profile_field(section, "recordingSplitFileTime") {
+		None => 15,
+		Some(raw) => match raw.parse::<i64>() {
+			Ok(v) if (1..=1440).contains(&v) => v,
+			_ => return Err(format!("recordingSplitFileTime '{raw}' must be 1-1440 minutes")),
+		},
+	};
+	let max_size_mb = match profile_field(section, "recordingSplitFileSize") {
+		None => 2048,
+		Some(raw) => match raw.parse::<i64>() {
+			Ok(v) if (20..=1_048_576).contains(&v) => v,
+			_ => return Err(format!("recordingSplitFileSize '{raw}' must be at least 20 MB")),
+		},
+	};
+	Ok(Some(RecordingSplitSettings {
+		mode,
+		max_time_sec: if mode == RecordingSplitMode::Time { minutes * 60 } else { 0 },
+		max_size_mb: if mode == RecordingSplitMode::Size { max_size_mb } else { 0 },
+	}))
+}
+
+/// `ui_profile.output.recording.recordingAutoRemux*`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct RecordingRemuxSettings {
+	pub(crate) auto_remux: bool,
+	pub(crate) delete_source: bool,
+}
+
+pub(crate) fn recording_remux_settings(settings: &crate::AppSettings) -> RecordingRemuxSettings {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	RecordingRemuxSettings {
+		auto_remux: profile_bool(section, "recordingAutoRemux").unwrap_or(false),
+		delete_source: profile_bool(section, "recordingAutoRemuxDeleteSource").unwrap_or(false),
+	}
+}
+
+/// A container the recording muxer can write and the codecs it accepts
+/// (empty lists accept anything libobs can encode).
+pub(crate) struct RecordingContainer {
+	pub(crate) name: &'static str,
+	pub(crate) extension: &'static str,
+	/// FFmpeg muxer name, used by ffmpeg_output.
+	pub(crate) format_name: &'static str,
+	pub(crate) muxer_settings: Option<&'static str>,
+	pub(crate) video_codecs: &'static [&'static str],
+	pub(crate) audio_codecs: &'static [&'static str],
+}
+
+const FRAGMENTED_MOVFLAGS: &str = "movflags=frag_keyframe+empty_moov+delay_moov";
+
+pub(crate) const RECORDING_CONTAINERS: &[RecordingContainer] = &[
+	RecordingContainer {
+		name: "mkv",
+		extension: "mkv",
+		format_name: "matroska",
+		muxer_settings: None,
+		video_codecs: &[],
+		audio_codecs: &[],
+	},
+	RecordingContainer {
+		name: "mp4",
+		extension: "mp4",
+		format_name: "mp4",
+		muxer_settings: None,
+		video_codecs: &["h264", "hevc", "av1"],
+		audio_codecs: &["aac", "opus", "flac", "alac"],
+	},
+	RecordingContainer {
+		name: "fragmented_mp4",
+		extension: "mp4",
+		format_name: "mp4",
+		muxer_settings: Some(FRAGMENTED_MOVFLAGS),
+		video_codecs: &["h264", "hevc", "av1"],
+		audio_codecs: &["aac", "opus", "flac", "alac"],
+	},
+	RecordingContainer {
+		name: "mov",
+		extension: "mov",
+		format_name: "mov",
+		muxer_settings: None,
+		video_codecs: &["h264", "hevc", "prores"],
+		audio_codecs: &["aac", "alac", "pcm"],
+	},
+	RecordingContainer {
+		name: "fragmented_mov",
+		extension: "mov",
+		format_name: "mov",
+		muxer_settings: Some(FRAGMENTED_MOVFLAGS),
+		video_codecs: &["h264", "hevc", "prores"],
+		audio_codecs: &["aac", "alac", "pcm"],
+	},
+	RecordingContainer {
+		name: "flv",
+		extension: "flv",
+		format_name: "flv",
+		muxer_settings: None,
+		video_codecs: &["h264"],
+		audio_codecs: &["aac"],
+	},
+	RecordingContainer {
+		name: "ts",
+		extension: "ts",
+		format_name: "mpegts",
+		muxer_settings: None,
+		video_codecs: &["h264", "hevc", "av1"],
+		audio_codecs: &["aac", "opus"],
+	},
+	RecordingContainer {
+		name: "m3u8",
+		extension: "m3u8",
+		format_name: "hls",
+		muxer_settings: None,
+		video_codecs: &["h264", "hevc"],
+		audio_codecs: &["aac"],
+	},
+	RecordingContainer {
+		name: "webm",
+		extension: "webm",
+		format_name: "webm",
+		muxer_settings: None,
+		video_codecs: &["vp8", "vp9", "av1"],
+		audio_codecs: &["opus", "vorbis"],
+	},
+	RecordingContainer {
+		name: "avi",
+		extension: "avi",
+		format_name: "avi",
+		muxer_settings: None,
+		video_codecs: &["h264"],
+		audio_codecs: &["aac", "pcm"],
+	},
+];
+
+/// Looks a container up by name, extension or FFmpeg muxer name.
+pub(crate) fn recording_container(name: &str) -> Option<&'static RecordingContainer> {
+	let name = name.trim().trim_start_matches('.');
+	RECORDING_CONTAINERS
+		.iter()
+		.find(|c| c.name.eq_ignore_ascii_case(name))
+		.or_else(|| {
+			RECORDING_CONTAINERS.iter().find(|c| {
+				c.extension.eq_ignore_ascii_case(name) || c.format_name.eq_ignore_ascii_case(name)
+			})
+		})
+}
+
+pub(crate) fn validate_recording_codecs(
+	container: &RecordingContainer,
+	video_codec: &str,
+	audio_codec: &str,
+) -> Result<(), String> {
+	let mut errors: Vec<String> = Vec::new();
+	if !container.video_codecs.is_empty()
+		&& !container.video_codecs.iter().any(|c| c.eq_ignore_ascii_case(video_codec))
+	{
+		errors.push(format!(
+			"{} cannot hold {video_codec} video (supported: {})",
+			container.name,
+			container.video_codecs.join(", ")
+		));
+	}
+	if !container.audio_codecs.is_empty()
+		&& !container.audio_codecs.iter().any(|c| c.eq_ignore_ascii_case(audio_codec))
+	{
+		errors.push(format!(
+			"{} cannot hold {audio_codec} audio (supported: {})",
+			container.name,
+			container.audio_codecs.join(", ")
+		));
+	}
+	if !errors.is_empty() {
+		return Err(format!("Invalid recording format: {}", errors.join("; ")));
+	}
+	Ok(())
+}
+
+/// Container, muxer and encoder choices from `ui_profile.output.recording`.
+pub(crate) struct RecordingOutputSettings {
+	/// `None` keeps the extension of the requested path.
+	pub(crate) container: Option<&'static RecordingContainer>,
+	/// Space-separated `key=value` pairs handed to the muxer.
+	pub(crate) muxer_settings: Vec<(String, String)>,
+	/// Encoder ids or UI aliases (`x264`, `aac`, ...); `None` uses the defaults.
+	pub(crate) video_encoder: Option<String>,
+	pub(crate) audio_encoder: Option<String>,
+	pub(crate) video_bitrate: i64,
+}
+
+impl RecordingOutputSettings {
+	/// Container defaults first so user-supplied keys override them.
+	pub(crate) fn muxer_settings_string(&self) -> String {
+		self.container
+			.and_then(|c| c.muxer_settings)
+			.map(str::to_string)
+			.into_iter()
+			.chain(self.muxer_settings.iter().map(|(k, v)| format!("{k}={v}")))
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+}
+
+pub(crate) fn parse_muxer_settings(raw: &str) -> Result<Vec<(String, String)>, String> {
+	raw.split_whitespace()
+		.map(|token| match token.split_once('=') {
+			Some((key, value)) if !key.trim().is_empty() => {
+				Ok((key.trim().to_string(), value.trim().to_string()))
+			}
+			_ => Err(format!("invalid muxer option '{token}' (expected key=value)")),
+		})
+		.collect()
+}
+
+pub(crate) fn recording_output_settings(
+	settings: &crate::AppSettings,
+) -> Result<RecordingOutputSettings, String> {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	let advanced = profile_bool(section, "recordingAdvanced").unwrap_or(false);
+	let mut errors: Vec<String> = Vec::new();
+
+	let container = match profile_field(section, "recordingFormat") {
+		None => None,
+		Some(raw) => match recording_container(&raw) {
+			Some(container) => Some(container),
+			None => {
+				let known: Vec<&str> = RECORDING_CONTAINERS.iter().map(|c| c.name).collect();
+				errors.push(format!("recordingFormat '{raw}' is not one of {}", known.join(", ")));
+				None
+			}
+		},
+	};
+	let muxer_settings = match profile_field(section, "recordingCustomMuxerSettings")
+		.filter(|_| advanced)
+	{
+		None => Vec::new(),
+		Some(raw) => parse_muxer_settings(&raw).unwrap_or_else(|err| {
+			errors.push(format!("recordingCustomMuxerSettings: {err}"));
+			Vec::new()
+		}),
+	};
+	let video_bitrate = match profile_field(section, "recordingVideoBitrate") {
+		None => 2500,
+		Some(raw) => match raw.parse::<i64>() {
+			Ok(v) if (1..=500_000).contains(&v) => v,
+			_ => {
+				errors.push(format!("recordingVideoBitrate '{raw}' is not a bitrate in kbps"));
+				2500
+			}
+		},
+	};
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid recording settings: {}", errors.join("; ")));
+	}
+
+	Ok(RecordingOutputSettings {
+		container,
+		muxer_settings,
+		video_encoder: profile_field(section, "recordingVideoEncoder").filter(|_| advanced),
+		audio_encoder: profile_field(section, "recordingAudioEncoder").filter(|_| advanced),
+		video_bitrate,
+	})
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RecordingCustomTarget {
+	File,
+	Url,
+}
+
+/// Custom FFmpeg output (`recordingCustomOutput`): any FFmpeg format written
+/// to a file or URL by `ffmpeg_output`, with FFmpeg encoder names.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RecordingCustomOutputSettings {
+	pub(crate) target: RecordingCustomTarget,
+	/// `recordingPathCustom`; file mode falls back to the requested path.
+	pub(crate) path: Option<String>,
+	pub(crate) format_name: String,
+	pub(crate) extension: String,
+	pub(crate) muxer_settings: String,
+	pub(crate) video_encoder: String,
+	pub(crate) video_settings: String,
+	pub(crate) video_bitrate: i64,
+	pub(crate) audio_encoder: String,
+	pub(crate) audio_settings: String,
+	pub(crate) audio_bitrate: i64,
+}
+
+pub(crate) fn recording_custom_output_settings(
+	settings: &crate::AppSettings,
+) -> Result<Option<RecordingCustomOutputSettings>, String> {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	if !profile_bool(section, "recordingCustomOutput").unwrap_or(false) {
+		return Ok(None);
+	}
+	let mut errors: Vec<String> = Vec::new();
+
+	let target = match profile_field(section, "recordingTypeOutput")
+		.map(|v| v.to_lowercase())
+		.as_deref()
+	{
+		None | Some("file") => RecordingCustomTarget::File,
+		Some("url") => RecordingCustomTarget::Url,
+		Some(other) => {
+			errors.push(format!("recordingTypeOutput '{other}' must be file or url"));
+			RecordingCustomTarget::File
+		}
+	};
+	let path = profile_field(section, "recordingPathCustom");
+	if target == RecordingCustomTarget::Url && path.is_none() {
+		errors.push("recordingPathCustom must hold the output URL".to_string());
+	}
+
+	// Known containers map to their FFmpeg muxer; anything else (nut, mxf, ...)
+	// is passed to FFmpeg as-is.
+	let format = profile_field(section, "recordingContainerFormat")
+		.map(|v| v.to_lowercase())
+		.unwrap_or_else(|| "mkv".to_string());
+	let (format_name, extension) = match recording_container(&format) {
+		Some(container) => (container.format_name.to_string(), container.extension.to_string()),
+		None if format.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+			(format.clone(), format.clone())
+		}
+		None => {
+			errors.push(format!("recordingContainerFormat '{format}' is not an FFmpeg format name"));
+			(format.clone(), format.clone())
+		}
+	};
+
+	let mut option_string = |key: &str| -> String {
+		let Some(raw) = profile_field(section, key) else {
+			return String::new();
+		};
+		match parse_muxer_settings(&raw) {
+			Ok(pairs) => pairs
+				.iter()
+				.map(|(k, v)| format!("{k}={v}"))
+				.collect::<Vec<_>>()
+				.join(" "),
+			Err(err) => {
+				errors.push(format!("{key}: {err}"));
+				String::new()
+			}
+		}
+	};
+	let muxer_settings = option_string("recordingMuxerSettings");
+	let video_settings = option_string("recordingVideoEncoderSettings");
+	let audio_settings = option_string("recordingAudioEncoderSettings");
+
+	let mut bitrate = |key: &str, default: i64| -> i64 {
+		match profile_field(section, key) {
+			None => default,
+			Some(raw) => match raw.parse::<i64>() {
+				Ok(v) if (1..=500_000).contains(&v) => v,
+				_ => {
+					errors.push(format!("{key} '{raw}' is not a bitrate in kbps"));
+					default
+				}
+			},
+		}
+	};
+	let video_bitrate = bitrate("recordingVideoBitrate", 2500);
+	let audio_bitrate = bitrate("recordingAudioBitrate", 160);
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid custom recording output: {}", errors.join("; ")));
+	}
+
+	Ok(Some(RecordingCustomOutputSettings {
+		target,
+		path,
+		format_name,
+		extension,
+		muxer_settings,
+		video_encoder: profile_field(section, "recordingVideoEncoderCustom")
+			.unwrap_or_else(|| "libx264".to_string()),
+		video_settings,
+		video_bitrate,
+		audio_encoder: profile_field(section, "recordingAudioEncoderCustom")
+			.unwrap_or_else(|| "aac".to_string()),
+		audio_settings,
+		audio_bitrate,
+	}))
+}
+
+/// Free-space limits for outputs writing to disk, from
+/// `ui_profile.output.recording.recordingLowDisk*` (values in MB).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct DiskSpaceSettings {
+	/// Descending; one warning is emitted per threshold crossed.
+	pub(crate) warn_mb: Vec<u64>,
+	/// Outputs are stopped below this; 0 disables the hard limit.
+	pub(crate) stop_mb: u64,
+}
+
+impl Default for DiskSpaceSettings {
+	fn default() -> Self {
+		Self {
+			warn_mb: vec![5120, 2048, 1024],
+			stop_mb: 512,
+		}
+	}
+}
+
+pub(crate) fn disk_space_settings(settings: &crate::AppSettings) -> Result<DiskSpaceSettings, String> {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	let mut out = DiskSpaceSettings::default();
+	let mut errors: Vec<String> = Vec::new();
+
+	if let Some(raw) = profile_field(section, "recordingLowDiskWarningMb") {
+		let parsed: Result<Vec<u64>, _> = raw
+			.split(|c: char| c == ',' || c.is_whitespace())
+			.filter(|t| !t.is_empty())
+			.map(|t| t.parse::<u64>())
+			.collect();
+		match parsed {
+			Ok(mut values) => {
+				values.retain(|v| *v > 0);
+				values.sort_unstable_by(|a, b| b.cmp(a));
+				values.dedup();
+				out.warn_mb = values;
+			}
+			Err(_) => errors.push(format!(
+				"recordingLowDiskWarningMb '{raw}' must be a comma-separated list of MB values"
+			)),
+		}
+	}
+	if let Some(raw) = profile_field(section, "recordingLowDiskStopMb") {
+		match raw.parse::<u64>() {
+			Ok(v) => out.stop_mb = v,
+			Err(_) => errors.push(format!("recordingLowDiskStopMb '{raw}' must be a size in MB")),
+		}
+	}
+	if let Some(lowest) = out.warn_mb.last().copied() {
+		if out.stop_mb > 0 && lowest <= out.stop_mb {
+			errors.push(format!(
+				"recordingLowDiskWarningMb thresholds must stay above recordingLowDiskStopMb ({} MB)",
+				out.stop_mb
+			));
+		}
+	}
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid disk space settings: {}", errors.join("; ")));
+	}
+	Ok(out)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ChapterSidecarFormat {
+	FfMetadata,
+	WebVtt,
+}
+
+/// `ui_profile.output.recording.recordingChapter*`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RecordingChapterSettings {
+	/// Used when the recording output has no native chapter support.
+	pub(crate) sidecar_format: ChapterSidecarFormat,
+	pub(crate) on_scene_switch: bool,
+}
+
+pub(crate) fn recording_chapter_settings(
+	settings: &crate::AppSettings,
+) -> Result<RecordingChapterSettings, String> {
+	let section = ui_profile_section(settings, &["output", "recording"]);
+	let sidecar_format = match profile_field(section, "recordingChapterFormat")
+		.map(|v| v.to_lowercase())
+		.as_deref()
+	{
+		None | Some("ffmetadata") => ChapterSidecarFormat::FfMetadata,
+		Some("webvtt") | Some("vtt") => ChapterSidecarFormat::WebVtt,
+		Some(other) => {
+			return Err(format!("recordingChapterFormat '{other}' must be ffmetadata or webvtt"))
+		}
+	};
+	Ok(RecordingChapterSettings {
+		sidecar_format,
+		on_scene_switch: profile_bool(section, "recordingChapterOnSceneSwitch").unwrap_or(false),
+	})
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+	if b == 0 {
+		a
+	} else {
+		gcd(b, a % b)
+	}
+}
+
+pub(crate) const MAX_VIDEO_FPS: f64 = 240.0;
+
+/// Parses `60`, `59.94` or `60000/1001` into a reduced fraction. NTSC
+/// decimals map to their exact x000/1001 rates.
+pub(crate) fn parse_fps(raw: &str) -> Option<(u32, u32)> {
+	let raw = raw.trim();
+	let (num, den) = if let Some((num, den)) = raw.split_once('/') {
+		(num.trim().parse::<u32>().ok()?, den.trim().parse::<u32>().ok()?)
+	} else if let Ok(whole) = raw.parse::<u32>() {
+		(whole, 1)
+	} else {
+		// Range-checked first so the scaled values below fit in u32.
+		let value = raw
+			.parse::<f64>()
+			.ok()
+			.filter(|v| v.is_finite() && *v > 0.0 && *v <= MAX_VIDEO_FPS)?;
+		let ntsc = (value * 1.001).round();
+		if (ntsc / 1.001 - value).abs() < 0.005 && ntsc.fract() == 0.0 {
+			(ntsc as u32 * 1000, 1001)
+		} else {
+			((value * 1000.0).round() as u32, 1000)
+		}
+	};
+	if num == 0 || den == 0 || num as f64 / den as f64 > MAX_VIDEO_FPS {
+		return None;
+	}
+	let divisor = gcd(num, den);
+	Some((num / divisor, den / divisor))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum VideoScaleType {
+	Bilinear,
+	Bicubic,
+	Lanczos,
+	Area,
+}
+
+impl VideoScaleType {
+	fn parse(raw: &str) -> Option<Self> {
+		match raw.trim().to_lowercase().as_str() {
+			"bilinear" => Some(Self::Bilinear),
+			"bicubic" => Some(Self::Bicubic),
+			"lanczos" => Some(Self::Lanczos),
+			"area" => Some(Self::Area),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum VideoColorFormat {
+	Nv12,
+	I420,
+	I444,
+	P010,
+	I010,
+	P216,
+	P416,
+	Bgra,
+}
+
+impl VideoColorFormat {
+	pub(crate) const ALL: [Self; 8] = [
+		Self::Nv12,
+		Self::I420,
+		Self::I444,
+		Self::P010,
+		Self::I010,
+		Self::P216,
+		Self::P416,
+		Self::Bgra,
+	];
+
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::Nv12 => "nv12",
+			Self::I420 => "i420",
+			Self::I444 => "i444",
+			Self::P010 => "p010",
+			Self::I010 => "i010",
+			Self::P216 => "p216",
+			Self::P416 => "p416",
+			Self::Bgra => "bgra",
+		}
+	}
+
+	fn parse(raw: &str) -> Option<Self> {
+		let raw = raw.trim().to_lowercase();
+		Self::ALL.into_iter().find(|f| f.as_str() == raw)
+	}
+
+	/// 10/16-bit formats; required for HDR and unsupported by 8-bit encoders.
+	pub(crate) fn is_high_bit_depth(self) -> bool {
+		matches!(self, Self::P010 | Self::I010 | Self::P216 | Self::P416)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum VideoColorSpace {
+	Rec601,
+	Rec709,
+	Srgb,
+	Rec2100Pq,
+	Rec2100Hlg,
+}
+
+impl VideoColorSpace {
+	pub(crate) const ALL: [Self; 5] = [
+		Self::Rec601,
+		Self::Rec709,
+		Self::Srgb,
+		Self::Rec2100Pq,
+		Self::Rec2100Hlg,
+	];
+
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::Rec601 => "601",
+			Self::Rec709 => "709",
+			Self::Srgb => "srgb",
+			Self::Rec2100Pq => "2100pq",
+			Self::Rec2100Hlg => "2100hlg",
+		}
+	}
+
+	fn parse(raw: &str) -> Option<Self> {
+		let raw = raw.trim().to_lowercase().replace(['.', ' ', '_', '-'], "");
+		let raw = raw.strip_prefix("rec").unwrap_or(&raw);
+		Self::ALL.into_iter().find(|c| c.as_str() == raw)
+	}
+
+	pub(crate) fn is_hdr(self) -> bool {
+		matches!(self, Self::Rec2100Pq | Self::Rec2100Hlg)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum VideoColorRange {
+	Partial,
+	Full,
+}
+
+impl VideoColorRange {
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::Partial => "partial",
+			Self::Full => "full",
+		}
+	}
+
+	fn parse(raw: &str) -> Option<Self> {
+		match raw.trim().to_lowercase().as_str() {
+			"partial" | "limited" => Some(Self::Partial),
+			"full" => Some(Self::Full),
+			_ => None,
+		}
+	}
+}
+
+pub(crate) const MAX_VIDEO_DIMENSION: u32 = 16384;
+
+/// Parses `WIDTHxHEIGHT`.
+pub(crate) fn parse_resolution(raw: &str) -> Option<(u32, u32)> {
+	let (width, height) = raw.trim().to_lowercase().split_once('x').and_then(|(w, h)| {
+		Some((w.trim().parse::<u32>().ok()?, h.trim().parse::<u32>().ok()?))
+	})?;
+	let valid = |v: u32| v > 0 && v <= MAX_VIDEO_DIMENSION;
+	(valid(width) && valid(height)).then_some((width, height))
+}
+
+/// Canvas settings from `ui_profile.video`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct VideoSettings {
+	pub(crate) fps_num: u32,
+	pub(crate) fps_den: u32,
+	/// Base resolution; `None` keeps the scene resolution.
+	pub(crate) canvas: Option<(u32, u32)>,
+	/// Scaled resolution handed to encoders; `None` encodes at canvas size.
+	pub(crate) output: Option<(u32, u32)>,
+	pub(crate) scale_type: VideoScaleType,
+	pub(crate) color_format: VideoColorFormat,
+	pub(crate) color_space: VideoColorSpace,
+	pub(crate) color_range: VideoColorRange,
+}
+
+impl Default for VideoSettings {
+	fn default() -> Self {
+		Self {
+			fps_num: 30,
+			fps_den: 1,
+			canvas: None,
+			output: None,
+			scale_type: VideoScaleType::Bicubic,
+			color_format: VideoColorFormat::Nv12,
+			color_space: VideoColorSpace::Rec709,
+			color_range: VideoColorRange::Partial,
+		}
+	}
+}
+
+impl VideoSettings {
+	/// Output size for a given canvas. Only downscaling is applied; an output
+	/// larger than the canvas (in either direction) encodes at canvas size.
+	pub(crate) fn output_for_canvas(&self, width: u32, height: u32) -> (u32, u32) {
+		match self.output {
+			Some((out_w, out_h)) if out_w <= width && out_h <= height => (out_w, out_h),
+			_ => (width, height),
+		}
+	}
+}
+
+pub(crate) fn video_settings(settings: &crate::AppSettings) -> Result<VideoSettings, String> {
+	let section = ui_profile_section(settings, &["video"]);
+	let mut out = VideoSettings::default();
+	let mut errors: Vec<String> = Vec::new();
+
+	let fps_type = profile_field(section, "videoFpsType").map(|v| v.to_lowercase());
+	let (key, raw) = match fps_type.as_deref() {
+		None | Some("common") => {
+			if profile_bool(section, "videoFpsCommonCustom").unwrap_or(false) {
+				("videoFpsCommonCustomValue", profile_field(section, "videoFpsCommonCustomValue"))
+			} else {
+				("videoFpsCommon", profile_field(section, "videoFpsCommon"))
+			}
+		}
+		Some("integer") => ("videoFpsIntegerValue", profile_field(section, "videoFpsIntegerValue")),
+		Some("fractional") => (
+			"videoFpsFractionalNumerator/Denominator",
+			profile_field(section, "videoFpsFractionalNumerator").map(|num| {
+				let den = profile_field(section, "videoFpsFractionalDenominator")
+					.unwrap_or_else(|| "1".to_string());
+				format!("{num}/{den}")
+			}),
+		),
+		Some(other) => {
+			errors.push(format!("videoFpsType '{other}' must be common, integer or fractional"));
+			("videoFpsType", None)
+		}
+	};
+	// Integer mode must not accept decimals even though parse_fps would.
+	let integer_only = fps_type.as_deref() == Some("integer");
+	if let Some(raw) = raw {
+		match parse_fps(&raw).filter(|(_, den)| !integer_only || *den == 1) {
+			Some((num, den)) => {
+				out.fps_num = num;
+				out.fps_den = den;
+			}
+			None => errors.push(format!(
+				"{key} '{raw}' is not a frame rate between 1 and {MAX_VIDEO_FPS} fps"
+			)),
+		}
+	}
+
+	for (key, target) in [
+		("videoCanvasResolution", &mut out.canvas),
+		("videoOutputResolution", &mut out.output),
+	] {
+		if let Some(raw) = profile_field(section, key) {
+			match parse_resolution(&raw) {
+				Some(resolution) => *target = Some(resolution),
+				None => errors.push(format!("{key} '{raw}' must be WIDTHxHEIGHT")),
+			}
+		}
+	}
+	if let Some(raw) = profile_field(section, "videoDownscaleFilter") {
+		match VideoScaleType::parse(&raw) {
+			Some(scale_type) => out.scale_type = scale_type,
+			None => errors.push(format!(
+				"videoDownscaleFilter '{raw}' must be bilinear, bicubic, lanczos or area"
+			)),
+		}
+	}
+
+	if let Some(raw) = profile_field(section, "videoColorFormat") {
+		match VideoColorFormat::parse(&raw) {
+			Some(format) => out.color_format = format,
+			None => errors.push(format!("videoColorFormat '{raw}' is not a supported format")),
+		}
+	}
+	if let Some(raw) = profile_field(section, "videoColorSpace") {
+		match VideoColorSpace::parse(&raw) {
+			Some(space) => out.color_space = space,
+			None => errors.push(format!(
+				"videoColorSpace '{raw}' must be 601, 709, srgb, 2100pq or 2100hlg"
+			)),
+		}
+	}
+	if let Some(raw) = profile_field(section, "videoColorRange") {
+		match VideoColorRange::parse(&raw) {
+			Some(range) => out.color_range = range,
+			None => errors.push(format!("videoColorRange '{raw}' must be partial or full")),
+		}
+	}
+	// libobs refuses Rec.2100 on 8-bit formats.
+	if out.color_space.is_hdr() && !out.color_format.is_high_bit_depth() {
+		errors.push(format!(
+			"videoColorSpace {} needs a 10-bit color format (p010, i010, p216 or p416), not {}",
+			out.color_space.as_str(),
+			out.color_format.as_str()
+		));
+	}
+
+	if !errors.is_empty() {
+		return Err(format!("Invalid video settings: {}", errors.join("; ")));
+	}
+	Ok(out)
+}
+
+/// Rejects high bit depth formats for codecs whose libobs encoders are all
+/// 8-bit (every H.264 encoder).
+pub(crate) fn validate_video_color_for_codec(
+	format: VideoColorFormat,
+	codec: &str,
+) -> Result<(), String> {
+	if format.is_high_bit_depth() && codec.eq_ignore_ascii_case("h264") {
+		return Err(format!(
+			"Invalid video settings: color format {} needs a 10-bit encoder (HEVC or AV1), H.264 encoders are 8-bit",
+			format.as_str()
+		));
+	}
+	Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum TransitionKind {
+	Cut,
+	Fade,
+	Swipe,
+	Slide,
+	FadeToColor,
+	LumaWipe,
+	Stinger,
+}
+
+impl TransitionKind {
+	pub(crate) const ALL: [Self; 7] = [
+		Self::Cut,
+		Self::Fade,
+		Self::Swipe,
+		Self::Slide,
+		Self::FadeToColor,
+		Self::LumaWipe,
+		Self::Stinger,
+	];
+
+	/// Kind as stored in the transitions profile.
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::Cut => "cut",
+			Self::Fade => "fade",
+			Self::Swipe => "swipe",
+			Self::Slide => "slide",
+			Self::FadeToColor => "fade_to_color",
+			Self::LumaWipe => "luma_wipe",
+			Self::Stinger => "stinger",
+		}
+	}
+
+	pub(crate) fn obs_id(self) -> &'static str {
+		match self {
+			Self::Cut => "cut_transition",
+			Self::Fade => "fade_transition",
+			Self::Swipe => "swipe_transition",
+			Self::Slide => "slide_transition",
+			Self::FadeToColor => "fade_to_color_transition",
+			Self::LumaWipe => "wipe_transition",
+			Self::Stinger => "obs_stinger_transition",
+		}
+	}
+
+	pub(crate) fn parse(raw: &str) -> Option<Self> {
+		let raw = raw.trim().to_lowercase();
+		let raw = match raw.as_str() {
+			"wipe" => "luma_wipe",
+			other => other,
+		};
+		Self::ALL.into_iter().find(|k| k.as_str() == raw)
+	}
+}
+
+pub(crate) const DEFAULT_TRANSITION_DURATION_MS: u32 = 300;
+pub(crate) const MAX_TRANSITION_DURATION_MS: u32 = 15_000;
+
+/// One entry of `ui_profile.tools.transitions.items`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct TransitionSettings {
+	pub(crate) id: String,
+	pub(crate) name: String,
+	/// Kind as stored; kept even when this build cannot run it.
+	pub(crate) raw_kind: String,
+	pub(crate) kind: Option<TransitionKind>,
+	pub(crate) duration_ms: u32,
+	pub(crate) params: std::collections::HashMap<String, String>,
+}
+
+impl TransitionSettings {
+	pub(crate) fn cut() -> Self {
+		Self {
+			id: "cut".to_string(),
+			name: "Cut".to_string(),
+			raw_kind: "cut".to_string(),
+			kind: Some(TransitionKind::Cut),
+			duration_ms: 0,
+			params: std::collections::HashMap::new(),
+		}
+	}
+
+	pub(crate) fn param(&self, key: &str) -> Option<&str> {
+		self.params.get(key).map(|v| v.trim()).filter(|v| !v.is_empty())
+	}
+}
+
+/// Same clamping as the transitions editor; cuts have no duration.
+pub(crate) fn transition_duration_ms(kind: Option<TransitionKind>, raw: Option<&str>) -> u32 {
+	if kind == Some(TransitionKind::Cut) {
+		return 0;
+	}
+	raw.and_then(|v| v.trim().parse::<f64>().ok())
+		.filter(|v| v.is_finite() && *v > 0.0)
+		.map(|v| (v.round() as u32).clamp(50, MAX_TRANSITION_DURATION_MS))
+		.unwrap_or(DEFAULT_TRANSITION_DURATION_MS)
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct TransitionsConfig {
+	pub(crate) items: Vec<TransitionSettings>,
+	pub(crate) active_id: Option<String>,
+}
+
+impl TransitionsConfig {
+	/// The selected transition, falling back to the first entry.
+	pub(crate) fn active(&self) -> Option<&TransitionSettings> {
+		self.active_id
+			.as_deref()
+			.and_then(|id| self.items.iter().find(|t| t.id == id))
+			.or_else(|| self.items.first())
+	}
+}
+
+pub(crate) fn transition_from_value(value: &serde_json::Value) -> Option<TransitionSettings> {
+	let row = value.as_object()?;
+	let text = |key: &str| {
+		row.get(key)
+			.and_then(|v| v.as_str())
+			.map(|v| v.trim().to_string())
+			.filter(|v| !v.is_empty())
+	};
+	let id = text("id")?;
+	let raw_kind = text("kind").unwrap_or_else(|| "fade".to_string());
+	let kind = TransitionKind::parse(&raw_kind);
+	let params: std::collections::HashMap<String, String> = row
+		.get("params")
+		.and_then(|v| v.as_object())
+		.map(|map| {
+			map.iter()
+				.map(|(k, v)| {
+					let value = match v {
+						serde_json::Value::String(s) => s.clone(),
+						serde_json::Value::Null => String::new(),
+						other => other.to_string(),
+					};
+					(k.clone(), value)
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+	Some(TransitionSettings {
+		name: text("name").unwrap_or_else(|| id.clone()),
+		id,
+		duration_ms: transition_duration_ms(kind, params.get("duration_ms").map(String::as_str)),
+		raw_kind,
+		kind,
+		params,
+	})
+}
+
+/// `ui_profile.tools.transitions`; entries without an id are skipped.
+pub(crate) fn transitions_config(settings: &crate::AppSettings) -> TransitionsConfig {
+	let section = ui_profile_section(settings, &["tools", "transitions"]);
+	let items = section
+		.and_then(|s| s.get("items"))
+		.and_then(|v| v.as_array())
+		.map(|list| list.iter().filter_map(transition_from_value).collect())
+		.unwrap_or_default();
+	TransitionsConfig {
+		items,
+		active_id: profile_field(section, "activeTransitionId"),
+	}
+}
+
+pub(crate) const MAX_STINGER_SEQUENCE_FPS: u32 = 240;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum StingerSource {
+	Media(std::path::PathBuf),
+	/// Folder of frames accepted by `transitions_validate_sequence_dir`.
+	Sequence { dir: std::path::PathBuf, fps: u32 },
+}
+
+/// How scene audio is faded while the stinger plays (libobs `audio_fade_style`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StingerAudioFade {
+	FadeOutIn,
+	Crossfade,
+}
+
+impl StingerAudioFade {
+	pub(crate) fn parse(raw: &str) -> Option<Self> {
+		match raw.trim().to_lowercase().as_str() {
+			"fade_out_in" => Some(Self::FadeOutIn),
+			"crossfade" => Some(Self::Crossfade),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StingerSettings {
+	pub(crate) source: StingerSource,
+	/// Where the scene cut happens, from the start of the stinger.
+	pub(crate) transition_point_ms: u32,
+	pub(crate) audio_fade: StingerAudioFade,
+}
+
+/// Params of a stinger entry: `source_mode`, `media_file`, `sequence_dir`,
+/// `sequence_fps`, `transition_point_ms` and `audio_fade`.
+pub(crate) fn stinger_settings(config: &TransitionSettings) -> Result<StingerSettings, String> {
+	let mut errors = Vec::new();
+	let source = match config.param("source_mode").unwrap_or("media") {
+		"sequence" => {
+			let dir = config.param("sequence_dir").map(std::path::PathBuf::from);
+			if dir.is_none() {
+				errors.push("sequence folder is required".to_string());
+			}
+			let fps = config
+				.param("sequence_fps")
+				.map(|v| v.parse::<u32>().ok().filter(|f| (1..=MAX_STINGER_SEQUENCE_FPS).contains(f)))
+				.unwrap_or(Some(30));
+			if fps.is_none() {
+				errors.push(format!("sequence fps must be 1-{MAX_STINGER_SEQUENCE_FPS}"));
+			}
+			dir.zip(fps).map(|(dir, fps)| StingerSource::Sequence { dir, fps })
+		}
+		"media" => {
+			let file = config
+				.param("media_file")
+				.map(|v| std::path::PathBuf::from(v.strip_prefix("file://").unwrap_or(v)));
+			if file.is_none() {
+				errors.push("stinger media file is required".to_string());
+			}
+			file.map(StingerSource::Media)
+		}
+		other => {
+			errors.push(format!("source mode '{other}' is not supported"));
+			None
+		}
+	};
+	let transition_point_ms = config
+		.param("transition_point_ms")
+		.map(|v| {
+			v.parse::<f64>()
+				.ok()
+				.filter(|ms| ms.is_finite() && *ms >= 0.0 && *ms <= MAX_TRANSITION_DURATION_MS as f64)
+				.map(|ms| ms.round() as u32)
+		})
+		.unwrap_or(Some(1000));
+	if transition_point_ms.is_none() {
+		errors.push(format!("transition point must be 0-{MAX_TRANSITION_DURATION_MS} ms"));
+	}
+	let audio_fade = config
+		.param("audio_fade")
+		.map(StingerAudioFade::parse)
+		.unwrap_or(Some(StingerAudioFade::FadeOutIn));
+	if audio_fade.is_none() {
+		errors.push("audio fade must be fade_out_in or crossfade".to_string());
+	}
+
+	match (source, transition_point_ms, audio_fade) {
+		(Some(source), Some(transition_point_ms), Some(audio_fade)) if errors.is_empty() => {
+			Ok(StingerSettings {
+				source,
+				transition_point_ms,
+				audio_fade,
+			})
+		}
+		_ => Err(format!("Invalid stinger settings: {}", errors.join("; "))),
+	}
+}
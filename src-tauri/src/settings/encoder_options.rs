@@ -105,6 +105,10 @@ pub(crate) fn defaults_obs_settings_select_options() -> crate::ObsSettingsSelect
 				value: "2.1".to_string(),
 				label: "2.1".to_string(),
 			},
+			crate::SelectOption {
+				value: "4.0".to_string(),
+				label: "4.0 (Quad)".to_string(),
+			},
 			crate::SelectOption {
 				value: "4.1".to_string(),
 				label: "4.1".to_string(),
@@ -145,6 +149,10 @@ pub(crate) fn defaults_obs_settings_select_options() -> crate::ObsSettingsSelect
 				value: "bilinear".to_string(),
 				label: "Bilinear (Fastest, soft)".to_string(),
 			},
+			crate::SelectOption {
+				value: "area".to_string(),
+				label: "Area (Box filter, best for large downscales)".to_string(),
+			},
 			crate::SelectOption {
 				value: "bicubic".to_string(),
 				label: "Bicubic (Sharpened scaling, 16 samples)".to_string(),
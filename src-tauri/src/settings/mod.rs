@@ -4,4 +4,5 @@ pub mod video_settings;
 pub mod audio_settings;
 pub mod profile_obs;
 pub mod plugins_profiles;
+pub mod services;
 pub mod themes;
@@ -5,7 +5,8 @@ pub(crate) fn export_profile_obs() -> Result<String, String> {
 		"profile_name": "RevoStream",
 		"stream": {
 			"server": settings.stream_url,
-			"key": settings.stream_key
+			"key": settings.stream_key,
+			"targets": settings.stream_targets
 		},
 		"output": {
 			"record_path": settings.record_path,
@@ -54,6 +55,14 @@ pub(crate) fn import_profile_obs(json: String) -> Result<String, String> {
 		if let Some(key) = stream.get("key").and_then(|v| v.as_str()) {
 			settings.stream_key = key.to_string();
 		}
+		if let Some(targets) = stream.get("targets").and_then(|v| v.as_array()) {
+			settings.stream_targets = Some(
+				targets
+					.iter()
+					.filter_map(|item| serde_json::from_value::<crate::StreamTargetConfig>(item.clone()).ok())
+					.collect(),
+			);
+		}
 	}
 
 	if let Some(output) = obj.get("output").and_then(|v| v.as_object()) {
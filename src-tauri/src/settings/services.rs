@@ -0,0 +1,272 @@
+// Typed view over data/conf/rtmp-services/services.json.
+
+fn json_i64(value: Option<&serde_json::Value>) -> Option<i64> {
+	let value = value?;
+	value
+		.as_i64()
+		.or_else(|| value.as_f64().map(|v| v as i64))
+		.or_else(|| value.as_str().and_then(|s| s.trim().parse::<i64>().ok()))
+}
+
+fn json_string_list(value: Option<&serde_json::Value>) -> Vec<String> {
+	value
+		.and_then(|v| v.as_array())
+		.map(|items| {
+			items
+				.iter()
+				.filter_map(|item| item.as_str())
+				.map(|item| item.trim().to_string())
+				.filter(|item| !item.is_empty())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn parse_service(value: &serde_json::Value) -> Option<crate::RtmpService> {
+	let obj = value.as_object()?;
+	let name = obj.get("name")?.as_str()?.trim().to_string();
+	if name.is_empty() {
+		return None;
+	}
+
+	let servers: Vec<crate::RtmpServiceServer> = obj
+		.get("servers")
+		.and_then(|v| v.as_array())
+		.map(|items| {
+			items
+				.iter()
+				.filter_map(|item| {
+					let url = item.get("url")?.as_str()?.trim().to_string();
+					if url.is_empty() {
+						return None;
+					}
+					let name = item
+						.get("name")
+						.and_then(|v| v.as_str())
+						.map(|v| v.trim().to_string())
+						.filter(|v| !v.is_empty())
+						.unwrap_or_else(|| url.clone());
+					Some(crate::RtmpServiceServer { name, url })
+				})
+				.collect()
+		})
+		.unwrap_or_default();
+	if servers.is_empty() {
+		return None;
+	}
+
+	// Older catalog entries omit "protocol"; infer it from the first server.
+	let protocol = obj
+		.get("protocol")
+		.and_then(|v| v.as_str())
+		.map(|v| v.trim().to_uppercase())
+		.filter(|v| !v.is_empty())
+		.unwrap_or_else(|| {
+			servers[0]
+				.url
+				.split("://")
+				.next()
+				.filter(|_| servers[0].url.contains("://"))
+				.unwrap_or("rtmp")
+				.to_uppercase()
+		});
+
+	let recommended = obj
+		.get("recommended")
+		.and_then(|v| v.as_object())
+		.map(|rec| crate::RtmpServiceRecommendations {
+			keyint: json_i64(rec.get("keyint")),
+			max_video_bitrate: json_i64(rec.get("max video bitrate")),
+			max_audio_bitrate: json_i64(rec.get("max audio bitrate")),
+			max_fps: json_i64(rec.get("max fps")),
+			bframes: json_i64(rec.get("bframes")),
+			profile: rec
+				.get("profile")
+				.and_then(|v| v.as_str())
+				.map(|v| v.trim().to_lowercase())
+				.filter(|v| !v.is_empty()),
+			supported_resolutions: json_string_list(rec.get("supported resolutions")),
+		})
+		.unwrap_or_default();
+
+	Some(crate::RtmpService {
+		name,
+		protocol,
+		servers,
+		recommended,
+		supported_video_codecs: json_string_list(obj.get("supported video codecs")),
+		supported_audio_codecs: json_string_list(obj.get("supported audio codecs")),
+	})
+}
+
+pub(crate) fn parse_rtmp_services(raw: &str) -> Result<Vec<crate::RtmpService>, String> {
+	let payload: serde_json::Value =
+		serde_json::from_str(raw).map_err(|e| format!("invalid services.json: {e}"))?;
+	let services = payload
+		.get("services")
+		.and_then(|v| v.as_array())
+		.ok_or_else(|| "services.json has no services array".to_string())?;
+	Ok(services.iter().filter_map(parse_service).collect())
+}
+
+pub(crate) fn list_rtmp_services() -> Result<Vec<crate::RtmpService>, String> {
+	let raw = super::core::load_rtmp_services_json()?;
+	parse_rtmp_services(&raw)
+}
+
+pub(crate) fn find_rtmp_service(name: &str) -> Result<crate::RtmpService, String> {
+	let name = name.trim();
+	list_rtmp_services()?
+		.into_iter()
+		.find(|s| s.name.eq_ignore_ascii_case(name))
+		.ok_or_else(|| format!("service not found in services.json: {name}"))
+}
+
+/// The catalog service owning the server `url` points at. The primary stream
+/// stores only a URL (often with the key appended), so the longest matching
+/// server prefix wins.
+pub(crate) fn find_rtmp_service_for_url(url: &str) -> Option<crate::RtmpService> {
+	let url = url.trim();
+	if url.is_empty() {
+		return None;
+	}
+	let mut best: Option<(usize, crate::RtmpService)> = None;
+	for service in list_rtmp_services().ok()? {
+		let matched = service
+			.servers
+			.iter()
+			.map(|s| s.url.trim_end_matches('/'))
+			.filter(|server| {
+				url.strip_prefix(server)
+					.is_some_and(|rest| rest.is_empty() || rest.starts_with('/') || rest.starts_with('?'))
+			})
+			.map(str::len)
+			.max();
+		if let Some(len) = matched {
+			if best.as_ref().map_or(true, |(best_len, _)| len > *best_len) {
+				best = Some((len, service));
+			}
+		}
+	}
+	best.map(|(_, service)| service)
+}
+
+/// Picks a server by name or URL, defaulting to the first one listed.
+pub(crate) fn resolve_service_server(
+	service: &crate::RtmpService,
+	server: Option<&str>,
+) -> Result<crate::RtmpServiceServer, String> {
+	let wanted = server.map(str::trim).filter(|s| !s.is_empty());
+	let Some(wanted) = wanted else {
+		return service
+			.servers
+			.first()
+			.cloned()
+			.ok_or_else(|| format!("service {} has no servers", service.name));
+	};
+	service
+		.servers
+		.iter()
+		.find(|s| s.url == wanted || s.name.eq_ignore_ascii_case(wanted))
+		.cloned()
+		.ok_or_else(|| format!("server '{wanted}' not found for service {}", service.name))
+}
+
+/// Encoder/video parameters a stream is about to go live with.
+pub(crate) struct ServiceCheckInput<'a> {
+	pub(crate) video_codec: &'a str,
+	pub(crate) audio_codec: &'a str,
+	pub(crate) video_bitrate: i64,
+	pub(crate) audio_bitrate: i64,
+	pub(crate) keyint_sec: i64,
+	pub(crate) bframes: Option<i64>,
+	pub(crate) profile: Option<&'a str>,
+	pub(crate) fps: Option<f64>,
+	pub(crate) output_resolution: Option<(u32, u32)>,
+}
+
+pub(crate) fn service_recommendation_warnings(
+	service: &crate::RtmpService,
+	input: &ServiceCheckInput,
+) -> Vec<String> {
+	let rec = &service.recommended;
+	let name = &service.name;
+	let mut warnings = Vec::new();
+
+	if !service.supported_video_codecs.is_empty()
+		&& !service
+			.supported_video_codecs
+			.iter()
+			.any(|c| c.eq_ignore_ascii_case(input.video_codec))
+	{
+		warnings.push(format!(
+			"{name} does not list video codec {} (supported: {})",
+			input.video_codec,
+			service.supported_video_codecs.join(", ")
+		));
+	}
+	if !service.supported_audio_codecs.is_empty()
+		&& !service
+			.supported_audio_codecs
+			.iter()
+			.any(|c| c.eq_ignore_ascii_case(input.audio_codec))
+	{
+		warnings.push(format!(
+			"{name} does not list audio codec {} (supported: {})",
+			input.audio_codec,
+			service.supported_audio_codecs.join(", ")
+		));
+	}
+	if let Some(max) = rec.max_video_bitrate.filter(|max| input.video_bitrate > *max) {
+		warnings.push(format!(
+			"video bitrate {} kbps exceeds {name} maximum of {max} kbps",
+			input.video_bitrate
+		));
+	}
+	if let Some(max) = rec.max_audio_bitrate.filter(|max| input.audio_bitrate > *max) {
+		warnings.push(format!(
+			"audio bitrate {} kbps exceeds {name} maximum of {max} kbps",
+			input.audio_bitrate
+		));
+	}
+	if let Some(keyint) = rec.keyint {
+		// 0 lets the encoder pick, which usually lands well above service limits.
+		if input.keyint_sec == 0 || input.keyint_sec > keyint {
+			warnings.push(format!(
+				"keyframe interval {}s exceeds {name} recommendation of {keyint}s",
+				input.keyint_sec
+			));
+		}
+	}
+	if let (Some(max), Some(bframes)) = (rec.bframes, input.bframes) {
+		if bframes > max {
+			warnings.push(format!("{bframes} B-frames exceeds {name} maximum of {max}"));
+		}
+	}
+	if let (Some(expected), Some(profile)) = (rec.profile.as_deref(), input.profile) {
+		if !expected.eq_ignore_ascii_case(profile) {
+			warnings.push(format!("{name} recommends the {expected} profile (using {profile})"));
+		}
+	}
+	if let (Some(max), Some(fps)) = (rec.max_fps, input.fps) {
+		if fps > max as f64 + 0.01 {
+			warnings.push(format!("{fps:.2} fps exceeds {name} maximum of {max} fps"));
+		}
+	}
+	if let Some((width, height)) = input.output_resolution {
+		let resolution = format!("{width}x{height}");
+		if !rec.supported_resolutions.is_empty()
+			&& !rec
+				.supported_resolutions
+				.iter()
+				.any(|r| r.split('@').next().unwrap_or(r).trim() == resolution)
+		{
+			warnings.push(format!(
+				"output resolution {resolution} is not supported by {name} (supported: {})",
+				rec.supported_resolutions.join(", ")
+			));
+		}
+	}
+
+	warnings
+}
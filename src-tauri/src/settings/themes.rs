@@ -191,7 +191,13 @@ pub(crate) fn transitions_validate_sequence_dir(path: String) -> Result<String,
 		return Err("folder path is empty".to_string());
 	}
 
-	let dir = std::path::PathBuf::from(trimmed);
+	let frames = sequence_frames(std::path::Path::new(trimmed))?;
+	Ok(format!("ok ({} images)", frames.len()))
+}
+
+/// Frames of a stinger image sequence in playback order (natural file name order,
+/// so `frame2` sorts before `frame10`).
+pub(crate) fn sequence_frames(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>, String> {
 	if !dir.is_dir() {
 		return Err("selected path is not a directory".to_string());
 	}
@@ -200,8 +206,8 @@ pub(crate) fn transitions_validate_sequence_dir(path: String) -> Result<String,
 		"png", "jpg", "jpeg", "webp", "bmp", "gif", "tif", "tiff", "tga", "exr", "svg",
 	];
 
-	let mut image_count = 0usize;
-	for entry in std::fs::read_dir(&dir).map_err(|e| format!("failed to read directory: {e}"))? {
+	let mut frames = Vec::new();
+	for entry in std::fs::read_dir(dir).map_err(|e| format!("failed to read directory: {e}"))? {
 		let entry = entry.map_err(|e| format!("failed to read entry: {e}"))?;
 		let entry_path = entry.path();
 
@@ -227,14 +233,35 @@ pub(crate) fn transitions_validate_sequence_dir(path: String) -> Result<String,
 			return Err(format!("folder contains non-image file: {bad}"));
 		}
 
-		image_count += 1;
+		frames.push(entry_path);
 	}
 
-	if image_count == 0 {
+	if frames.is_empty() {
 		return Err("folder does not contain any image files".to_string());
 	}
 
-	Ok(format!("ok ({image_count} images)"))
+	frames.sort_by_cached_key(|p| natural_sort_key(&p.file_name().unwrap_or_default().to_string_lossy()));
+	Ok(frames)
+}
+
+// Digit runs compare by value; everything else case-insensitively.
+fn natural_sort_key(name: &str) -> Vec<(String, u128)> {
+	let mut key = Vec::new();
+	let mut text = String::new();
+	let mut digits = String::new();
+	for ch in name.chars() {
+		if ch.is_ascii_digit() {
+			digits.push(ch);
+			continue;
+		}
+		if !digits.is_empty() {
+			key.push((std::mem::take(&mut text), digits.parse().unwrap_or(u128::MAX)));
+			digits.clear();
+		}
+		text.extend(ch.to_lowercase());
+	}
+	key.push((text, digits.parse().unwrap_or(0)));
+	key
 }
 
 pub(crate) fn themes_import(
@@ -1,3 +1,37 @@
+pub(crate) fn any_output_active(runtime: &crate::ObsRuntime) -> bool {
+	!runtime.output_record.is_null()
+		|| !runtime.output_stream.is_null()
+		|| !runtime.stream_targets.is_empty()
+		|| runtime.replay_buffer.is_some()
+}
+
+// Custom FFmpeg recordings pick their own pixel format, so only the profile's
+// libobs recording encoder (H.264 unless set) is checked.
+fn recording_video_codec(settings: &crate::AppSettings) -> Option<String> {
+	if let Ok(Some(_)) = crate::settings::core::recording_custom_output_settings(settings) {
+		return None;
+	}
+	let encoder = crate::settings::core::recording_output_settings(settings)
+		.ok()
+		.and_then(|config| config.video_encoder)
+		.and_then(|value| crate::runtime::encoders::resolve_encoder_id(&value).ok());
+	Some(
+		encoder
+			.as_deref()
+			.and_then(crate::runtime::encoders::encoder_codec)
+			.unwrap_or_else(|| "h264".to_string()),
+	)
+}
+
+fn profile_video_settings() -> Result<crate::settings::core::VideoSettings, String> {
+	let settings = crate::settings::core::settings_get()?;
+	let video = crate::settings::core::video_settings(&settings)?;
+	if let Some(codec) = recording_video_codec(&settings) {
+		crate::settings::core::validate_video_color_for_codec(video.color_format, &codec)?;
+	}
+	Ok(video)
+}
+
 pub(crate) fn set_scene_resolution(
 	state: tauri::State<crate::ObsState>,
 	resolution: String,
@@ -9,7 +43,7 @@ pub(crate) fn set_scene_resolution(
 	if !runtime.initialized {
 		return Err("OBS not initialized".to_string());
 	}
-	if !runtime.output_record.is_null() || !runtime.output_stream.is_null() {
+	if any_output_active(&runtime) {
 		return Err("Cannot change resolution while recording/streaming".to_string());
 	}
 
@@ -26,10 +60,132 @@ pub(crate) fn set_scene_resolution(
 		.parse()
 		.map_err(|_| "Invalid height".to_string())?;
 
-	let ok = crate::runtime::helpers::reset_video_with_resolution(width, height);
+	let video = profile_video_settings()?;
+	let ok = crate::runtime::helpers::reset_video_with_resolution(width, height, &video);
 	if !ok {
 		return Err("obs_reset_video failed".to_string());
 	}
 	runtime.scene_resolution = format!("{width}x{height}");
+	let (output_width, output_height) = video.output_for_canvas(width, height);
+	if (output_width, output_height) != (width, height) {
+		return Ok(format!(
+			"Resolution set to {width}x{height} (output {output_width}x{output_height})"
+		));
+	}
 	Ok(format!("Resolution set to {width}x{height}"))
 }
+
+/// Re-applies video with a new frame rate (`60`, `59.94`, `30000/1001`), or
+/// with the profile's frame rate when `fps` is not given.
+pub(crate) fn set_video_fps(
+	state: tauri::State<crate::ObsState>,
+	fps: Option<String>,
+) -> Result<String, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	if any_output_active(&runtime) {
+		return Err("Cannot change frame rate while recording/streaming".to_string());
+	}
+
+	let mut video = profile_video_settings()?;
+	let requested = match fps.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+		Some(raw) => Some(
+			crate::settings::core::parse_fps(raw)
+				.ok_or_else(|| format!("Invalid frame rate: {raw}"))?,
+		),
+		None => None,
+	};
+	if let Some((num, den)) = requested {
+		video.fps_num = num;
+		video.fps_den = den;
+	}
+
+	let (width, height) = current_base_resolution(&runtime)
+		.ok_or_else(|| "Scene resolution unknown".to_string())?;
+	if !crate::runtime::helpers::reset_video_with_resolution(width, height, &video) {
+		return Err("obs_reset_video failed".to_string());
+	}
+	// Later resets (set_scene_resolution on every settings save) read the
+	// profile, so the new rate has to live there too.
+	if let Some((num, den)) = requested {
+		persist_video_fps(num, den)
+			.map_err(|e| format!("Frame rate applied but not saved to the profile: {e}"))?;
+	}
+	let info = read_video_info().ok_or_else(|| "obs_get_video_info failed".to_string())?;
+	Ok(format!("Frame rate set to {}/{}", info.fps_num, info.fps_den))
+}
+
+fn persist_video_fps(num: u32, den: u32) -> Result<(), String> {
+	let mut settings = crate::settings::core::settings_get()?;
+	let video = crate::settings::core::ui_profile_section_mut(&mut settings, &["video"]);
+	if den == 1 {
+		video.insert("videoFpsType".to_string(), serde_json::json!("integer"));
+		video.insert("videoFpsIntegerValue".to_string(), serde_json::json!(num.to_string()));
+	} else {
+		video.insert("videoFpsType".to_string(), serde_json::json!("fractional"));
+		video.insert("videoFpsFractionalNumerator".to_string(), serde_json::json!(num.to_string()));
+		video.insert("videoFpsFractionalDenominator".to_string(), serde_json::json!(den.to_string()));
+	}
+	crate::settings::core::settings_save(settings)?;
+	crate::settings::core::notify_settings_changed();
+	Ok(())
+}
+
+fn current_base_resolution(runtime: &crate::ObsRuntime) -> Option<(u32, u32)> {
+	if let Some(info) = read_video_info().filter(|i| i.base_width > 0 && i.base_height > 0) {
+		return Some((info.base_width, info.base_height));
+	}
+	let (width, height) = runtime.scene_resolution.split_once('x')?;
+	Some((width.trim().parse().ok()?, height.trim().parse().ok()?))
+}
+
+fn read_video_info() -> Option<crate::VideoInfo> {
+	unsafe {
+		let mut ovi: revo_lib::obs::obs_video_info = std::mem::zeroed();
+		if !revo_lib::obs::obs_get_video_info(&mut ovi as *mut _) {
+			return None;
+		}
+		let fps = if ovi.fps_den == 0 {
+			0.0
+		} else {
+			ovi.fps_num as f64 / ovi.fps_den as f64
+		};
+		Some(crate::VideoInfo {
+			base_width: ovi.base_width,
+			base_height: ovi.base_height,
+			output_width: ovi.output_width,
+			output_height: ovi.output_height,
+			fps_num: ovi.fps_num,
+			fps_den: ovi.fps_den,
+			fps,
+			color_format: crate::runtime::helpers::color_format_from_obs(ovi.output_format)
+				.map(|f| f.as_str().to_string())
+				.unwrap_or_else(|| ovi.output_format.to_string()),
+			color_space: crate::runtime::helpers::color_space_from_obs(ovi.colorspace)
+				.map(|c| c.as_str().to_string())
+				.unwrap_or_else(|| ovi.colorspace.to_string()),
+			color_range: if ovi.range == revo_lib::obs::video_range_type_VIDEO_RANGE_FULL {
+				"full".to_string()
+			} else {
+				"partial".to_string()
+			},
+		})
+	}
+}
+
+/// What libobs is actually running with, as opposed to what the profile asks for.
+pub(crate) fn get_video_info(state: tauri::State<crate::ObsState>) -> Result<crate::VideoInfo, String> {
+	let runtime = state
+		.runtime
+		.lock()
+		.map_err(|_| "state poisoned".to_string())?;
+	if !runtime.initialized {
+		return Err("OBS not initialized".to_string());
+	}
+	read_video_info().ok_or_else(|| "obs_get_video_info failed".to_string())
+}
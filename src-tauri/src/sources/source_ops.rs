@@ -520,6 +520,7 @@ pub(crate) fn create_source_in_scene(
 	if requested_type.is_empty() {
 		return Err("source type required".to_string());
 	}
+	crate::runtime::headless::ensure_source_type_allowed(requested_type)?;
 	let create_type = resolve_creatable_source_type(requested_type);
 
 	unsafe {
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
 pub(crate) fn list_window_picker_items() -> Result<Vec<crate::SourceTypeItem>, String> {
+	if crate::runtime::headless::is_offscreen() {
+		return Ok(Vec::new());
+	}
 	let mut out: Vec<crate::SourceTypeItem> = Vec::new();
 	let mut seen: HashSet<String> = HashSet::new();
 
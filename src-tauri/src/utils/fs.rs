@@ -38,3 +38,17 @@ pub(crate) fn runtime_logs_root_dir() -> Result<PathBuf, String> {
     }
     Ok(data_dir.join("..").join("logs"))
 }
+
+// Bytes available to unprivileged users on the filesystem holding `path`;
+// walks up to the nearest existing ancestor so not-yet-created dirs work.
+pub(crate) fn available_space(path: &std::path::Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = std::ffi::CString::new(existing.to_string_lossy().as_bytes()).ok()?;
+    unsafe {
+        let mut stat: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_path.as_ptr(), &mut stat as *mut libc::statvfs) != 0 {
+            return None;
+        }
+        Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+    }
+}
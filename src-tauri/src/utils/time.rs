@@ -9,3 +9,39 @@ pub(crate) fn current_year_local() -> i32 {
         tm.tm_year + 1900
     }
 }
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LocalDateTime {
+    pub(crate) year: i32,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+}
+
+pub(crate) fn local_datetime_now() -> LocalDateTime {
+    unsafe {
+        let mut now: libc::time_t = 0;
+        libc::time(&mut now as *mut libc::time_t);
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now as *const libc::time_t, &mut tm as *mut libc::tm).is_null() {
+            return LocalDateTime {
+                year: 1970,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+            };
+        }
+        LocalDateTime {
+            year: tm.tm_year + 1900,
+            month: (tm.tm_mon + 1) as u32,
+            day: tm.tm_mday as u32,
+            hour: tm.tm_hour as u32,
+            minute: tm.tm_min as u32,
+            second: tm.tm_sec as u32,
+        }
+    }
+}